@@ -1,28 +1,46 @@
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::{DeriveInput, Generics, Ident};
+use quote::{quote, ToTokens};
+use syn::{DeriveInput, Generics, Ident, Visibility};
 
-use super::field::Field;
+use super::field::{contains_ident, Args, Context, Field};
+
+mod builder;
+mod typestate;
 
 pub struct Data {
+    vis: Visibility,
     name: Ident,
     generics: Generics,
+    args: Args,
+    members: Vec<(Ident, syn::Type)>,
     fields: Vec<Field>,
 }
 
 impl Data {
     pub fn from_derive_input(derive_input: DeriveInput) -> Self {
         if let syn::Data::Struct(data_struct) = derive_input.data {
+            let vis = derive_input.vis;
             let name = derive_input.ident;
             let generics = derive_input.generics;
+            let args = Args::from_struct_attrs(&derive_input.attrs)
+                .unwrap()
+                .unwrap_or_default();
+            let members = data_struct
+                .fields
+                .iter()
+                .filter_map(|field| Some((field.ident.clone()?, field.ty.clone())))
+                .collect();
             let fields = data_struct
                 .fields
                 .into_iter()
                 .filter_map(Field::from_field)
                 .collect();
             Self {
+                vis,
                 name,
                 generics,
+                args,
+                members,
                 fields,
             }
         } else {
@@ -31,27 +49,232 @@ impl Data {
     }
 
     pub fn generate_impl(&self) -> TokenStream2 {
+        assert!(
+            !self.args.typestate,
+            "'typestate' argument is only valid for `FluentBuilder`"
+        );
+        let default_impl = self.generate_default_impl();
+
         if self.fields.is_empty() {
-            return TokenStream2::default();
+            return default_impl.unwrap_or_default();
         }
 
         let name = &self.name;
-        let generics = &self.generics;
-        let setters: TokenStream2 = self.fields.iter().map(Field::generate_setter).collect();
+        let context = Context {
+            name,
+            generics: &self.generics,
+            fields: &self.members,
+            attrs: &self.args.attrs,
+            builder: false,
+            transition: None,
+        };
+        let setters: TokenStream2 = self
+            .fields
+            .iter()
+            .map(|field| field.generate_setter(&context))
+            .collect();
+
+        let constructor = self.generate_constructor();
+
+        let generics = self.bounded_generics();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         quote! {
-            impl#generics #name#generics {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #constructor
                 #setters
             }
+
+            #default_impl
+        }
+    }
+
+    /// Generate an implementation of `Default` from the default values of
+    /// each field, if the struct-level `#[set(impl_default)]` argument is
+    /// present.
+    fn generate_default_impl(&self) -> Option<TokenStream2> {
+        if !self.args.impl_default {
+            return None;
+        }
+
+        let name = &self.name;
+        let mut generics = self.bounded_generics();
+        let default_tys = self.default_tys(self.members.iter().map(|(member, _)| member));
+        if !default_tys.is_empty() {
+            generics.make_where_clause().predicates.extend(
+                default_tys
+                    .iter()
+                    .map(|ty| -> syn::WherePredicate { syn::parse_quote!(#ty: Default) }),
+            );
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let members = self.members.iter().map(|(member, _)| member);
+        let defaults = self
+            .members
+            .iter()
+            .map(|(member, _)| self.default_value(member));
+
+        Some(quote! {
+            impl #impl_generics Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#members: #defaults,)*
+                    }
+                }
+            }
+        })
+    }
+
+    /// Generate a constructor taking each of the required fields, with the
+    /// remaining fields set to their defaults.
+    ///
+    /// The constructor is only generated if there are required fields.
+    fn generate_constructor(&self) -> Option<TokenStream2> {
+        let required: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|field| field.is_required())
+            .collect();
+        if required.is_empty() {
+            return None;
+        }
+
+        let vis = &self.vis;
+        let params = required.iter().map(|field| field.generate_param());
+        let where_clause = self.default_where_clause(
+            self.members
+                .iter()
+                .map(|(member, _)| member)
+                .filter(|member| !required.iter().any(|field| field.name() == *member)),
+        );
+        let inits = self.members.iter().map(|(member, _)| {
+            if let Some(field) = required.iter().find(|field| field.name() == member) {
+                field.generate_init()
+            } else {
+                let default = self.default_value(member);
+                quote!(#member: #default)
+            }
+        });
+
+        Some(quote! {
+            /// Create a new value from its required fields, with the remaining
+            /// fields set to their defaults
+            #vis fn new(#(#params),*) -> Self #where_clause {
+                Self {
+                    #(#inits,)*
+                }
+            }
+        })
+    }
+
+    /// The expression for the default value of a field, from its
+    /// `#[set(default = "...")]` argument or `Default::default()`.
+    fn default_value(&self, member: &Ident) -> TokenStream2 {
+        self.default_expr(member)
+            .map_or_else(|| quote!(Default::default()), ToTokens::to_token_stream)
+    }
+
+    /// The expression which takes the value of a field from a builder,
+    /// falling back to its default if it hasn't been set.
+    fn unwrap_or_default(&self, member: &Ident) -> TokenStream2 {
+        if let Some(default) = self.default_expr(member) {
+            quote!(self.#member.unwrap_or_else(|| #default))
+        } else {
+            quote!(self.#member.unwrap_or_default())
+        }
+    }
+
+    /// The initialisers of the fields of a builder which aren't required,
+    /// falling back to their defaults, and the where-clause `build` needs to
+    /// fill them.
+    fn optional_inits(&self, required: &[&Ident]) -> (Vec<TokenStream2>, Option<TokenStream2>) {
+        let optional: Vec<_> = self
+            .members
+            .iter()
+            .map(|(member, _)| member)
+            .filter(|member| !required.contains(member))
+            .collect();
+        let inits = optional
+            .iter()
+            .map(|member| {
+                let value = self.unwrap_or_default(member);
+                quote!(#member: #value)
+            })
+            .collect();
+        (inits, self.default_where_clause(optional.into_iter()))
+    }
+
+    /// The expression from the `#[set(default = "...")]` argument of a field,
+    /// if any
+    fn default_expr(&self, member: &Ident) -> Option<&syn::Expr> {
+        self.fields
+            .iter()
+            .find(|field| field.name() == member)
+            .and_then(Field::default_expr)
+    }
+
+    /// The types of the given fields which fall back to `Default::default()`
+    /// and use one of the struct's type parameters, so may only implement
+    /// `Default` for some of its arguments.
+    ///
+    /// Bounding the field types rather than the type parameters, unlike
+    /// `#[derive(Default)]`, keeps fields like `Option<T>` from requiring
+    /// `T: Default`.
+    fn default_tys<'a>(&'a self, members: impl Iterator<Item = &'a Ident>) -> Vec<&'a syn::Type> {
+        let mut tys = Vec::new();
+        for member in members.filter(|member| self.default_expr(member).is_none()) {
+            let ty = self
+                .members
+                .iter()
+                .find(|(name, _)| name == member)
+                .map(|(_, ty)| ty);
+            if let Some(ty) = ty.filter(|ty| self.uses_type_params(ty) && !tys.contains(ty)) {
+                tys.push(ty);
+            }
+        }
+        tys
+    }
+
+    /// Whether a type uses any of the struct's type parameters
+    fn uses_type_params(&self, ty: &syn::Type) -> bool {
+        self.generics
+            .type_params()
+            .any(|param| contains_ident(ty.to_token_stream(), &param.ident))
+    }
+
+    /// A where-clause for a method which fills the given fields from their
+    /// defaults, bounding those whose types use type parameters by `Default`.
+    fn default_where_clause<'a>(
+        &'a self,
+        members: impl Iterator<Item = &'a Ident>,
+    ) -> Option<TokenStream2> {
+        let tys = self.default_tys(members);
+        if tys.is_empty() {
+            None
+        } else {
+            Some(quote!(where #(#tys: Default),*))
         }
     }
+
+    /// The generics of the struct, with the where-predicates from the
+    /// struct-level `#[set(bound = "...")]` argument added.
+    fn bounded_generics(&self) -> Generics {
+        let mut generics = self.generics.clone();
+        if !self.args.bounds.is_empty() {
+            generics
+                .make_where_clause()
+                .predicates
+                .extend(self.args.bounds.iter().cloned());
+        }
+        generics
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use proc_macro2::TokenStream as TokenStream2;
-    use quote::quote;
+    use quote::{format_ident, quote};
     use syn::{
         parse::{Parse, Parser},
         DeriveInput,
@@ -67,6 +290,7 @@ mod tests {
         },
         &quote! {
             impl MyStruct {
+                #[must_use]
                 fn a(mut self, a: u32) -> Self {
                     self.a = a;
                     self
@@ -84,6 +308,7 @@ mod tests {
         },
         &quote! {
             impl MyStruct {
+                #[must_use]
                 fn a(mut self, a: impl Into<u32>) -> Self {
                     self.a = a.into();
                     self
@@ -103,6 +328,7 @@ mod tests {
         &quote! {
             impl MyStruct {
                 #[doc = " This has a comment"]
+                #[must_use]
                 fn a(mut self, a: u32) -> Self {
                     self.a = a;
                     self
@@ -120,6 +346,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self, a: T) -> Self {
                     self.a = a;
                     self
@@ -137,6 +364,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self, a: T) -> Self {
                     self.a = Some(a);
                     self
@@ -154,6 +382,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self, a: impl Into<T>) -> Self {
                     self.a = Some(a.into());
                     self
@@ -171,6 +400,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self) -> Self {
                     self.a = true;
                     self
@@ -188,6 +418,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self) -> Self {
                     self.a = true;
                     self
@@ -205,6 +436,7 @@ mod tests {
         },
         &quote! {
             impl<T> MyStruct<T> {
+                #[must_use]
                 fn a(mut self) -> Self {
                     self.a = true;
                     self
@@ -213,12 +445,1497 @@ mod tests {
         } => panics
         ; "strip other type"
     )]
-    fn parse(input: TokenStream2, expected: &TokenStream2) {
-        let parser = DeriveInput::parse;
-        let derive_input = parser.parse2(input).unwrap();
-
-        let data = Data::from_derive_input(derive_input);
-
-        assert_eq!(data.generate_impl().to_string(), expected.to_string());
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, pub)]
+                a: u32
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                pub const fn a(mut self, a: u32) -> Self {
+                    self.a = a;
+                    self
+                }
+            }
+        }
+        ; "const setter"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, strip)]
+                a: Option<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                const fn a(mut self, a: u32) -> Self {
+                    self.a = Some(a);
+                    self
+                }
+            }
+        }
+        ; "const strip option"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, strip)]
+                a: bool
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                const fn a(mut self) -> Self {
+                    self.a = true;
+                    self
+                }
+            }
+        }
+        ; "const strip bool"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, into)]
+                a: u32
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                const fn a(mut self, a: impl Into<u32>) -> Self {
+                    self.a = a.into();
+                    self
+                }
+            }
+        } => panics
+        ; "const into"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, try_into)]
+                a: u32
+            }
+        },
+        &quote! {} => panics
+        ; "const try into"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(const, strip, clear)]
+                a: Option<String>
+            }
+        },
+        &quote! {} => panics
+        ; "const needs drop"
+    )]
+    #[test_case(
+        quote! {
+            #[set(attr(inline))]
+            struct MyStruct {
+                #[set(attr(track_caller, cfg(test)))]
+                a: u32,
+                #[set]
+                b: u32
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                #[inline]
+                #[track_caller]
+                #[cfg(test)]
+                fn a(mut self, a: u32) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[must_use]
+                #[inline]
+                fn b(mut self, b: u32) -> Self {
+                    self.b = b;
+                    self
+                }
+            }
+        }
+        ; "forwarded attributes"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<T> {
+                #[set(strip, into, outline, attr(inline))]
+                a: Option<T>
+            }
+        },
+        &quote! {
+            impl<T> MyStruct<T> {
+                #[must_use]
+                #[inline]
+                fn a(self, a: impl Into<T>) -> Self {
+                    self.__set_a(a.into())
+                }
+                #[inline(never)]
+                fn __set_a(mut self, a: T) -> Self {
+                    self.a = Some(a);
+                    self
+                }
+            }
+        }
+        ; "outline"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(outline)]
+                a: u32
+            }
+        },
+        &quote! {} => panics
+        ; "outline without into"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<T> {
+                #[set(into, generic)]
+                a: T
+            }
+        },
+        &quote! {
+            impl<T> MyStruct<T> {
+                #[must_use]
+                fn a<V: Into<T>>(mut self, a: V) -> Self {
+                    self.a = a.into();
+                    self
+                }
+            }
+        }
+        ; "generic"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<V, V0> {
+                #[set(into, generic, outline)]
+                a: V,
+                b: V0
+            }
+        },
+        &quote! {
+            impl<V, V0> MyStruct<V, V0> {
+                #[must_use]
+                fn a<V1: Into<V>>(self, a: V1) -> Self {
+                    self.__set_a(a.into())
+                }
+                #[inline(never)]
+                fn __set_a(mut self, a: V) -> Self {
+                    self.a = a;
+                    self
+                }
+            }
+        }
+        ; "generic outline shadowing"
+    )]
+    #[test_case(
+        quote! {
+            #[set(bound = "T: Clone")]
+            struct MyStruct<T: Default> where T: Copy {
+                #[set(bound = "T: PartialEq")]
+                a: T
+            }
+        },
+        &quote! {
+            impl<T: Default> MyStruct<T> where T: Copy, T: Clone {
+                #[must_use]
+                fn a(mut self, a: T) -> Self where T: PartialEq {
+                    self.a = a;
+                    self
+                }
+            }
+        }
+        ; "bounds"
+    )]
+    #[test_case(
+        quote! {
+            struct Client<'a, T: Clone> where T: Default {
+                #[set(retype)]
+                transport: T,
+                name: &'a str,
+                #[set]
+                retries: u32
+            }
+        },
+        &quote! {
+            impl<'a, T: Clone> Client<'a, T> where T: Default {
+                #[must_use]
+                fn transport<U: Clone>(self, transport: U) -> Client<'a, U> where U: Default {
+                    Client {
+                        transport,
+                        name: self.name,
+                        retries: self.retries,
+                    }
+                }
+                #[must_use]
+                fn retries(mut self, retries: u32) -> Self {
+                    self.retries = retries;
+                    self
+                }
+            }
+        }
+        ; "retype"
+    )]
+    #[test_case(
+        quote! {
+            struct Client<T> {
+                #[set(retype)]
+                transport: T,
+                fallback: Option<T>
+            }
+        },
+        &quote! {} => panics
+        ; "retype shared type parameter"
+    )]
+    #[test_case(
+        quote! {
+            struct Client<T> {
+                #[set(retype)]
+                transport: Option<T>
+            }
+        },
+        &quote! {} => panics
+        ; "retype non type parameter"
+    )]
+    #[test_case(
+        quote! {
+            pub struct MyStruct {
+                #[set(required, into)]
+                a: String,
+                #[set(required)]
+                b: u32,
+                #[set(default = "8080")]
+                c: u16,
+                d: Vec<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                /// Create a new value from its required fields, with the remaining
+                /// fields set to their defaults
+                pub fn new(a: impl Into<String>, b: u32) -> Self {
+                    Self {
+                        a: a.into(),
+                        b,
+                        c: 8080,
+                        d: Default::default(),
+                    }
+                }
+                #[must_use]
+                fn a(mut self, a: impl Into<String>) -> Self {
+                    self.a = a.into();
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: u32) -> Self {
+                    self.b = b;
+                    self
+                }
+                #[must_use]
+                fn c(mut self, c: u16) -> Self {
+                    self.c = c;
+                    self
+                }
+            }
+        }
+        ; "constructor"
+    )]
+    #[test_case(
+        quote! {
+            pub struct MyStruct<T, U> {
+                #[set(required)]
+                a: T,
+                b: Option<T>,
+                c: U,
+                #[set(default = "Vec::new()")]
+                d: Vec<U>,
+                e: u32
+            }
+        },
+        &quote! {
+            impl<T, U> MyStruct<T, U> {
+                /// Create a new value from its required fields, with the remaining
+                /// fields set to their defaults
+                pub fn new(a: T) -> Self where Option<T>: Default, U: Default {
+                    Self {
+                        a,
+                        b: Default::default(),
+                        c: Default::default(),
+                        d: Vec::new(),
+                        e: Default::default(),
+                    }
+                }
+                #[must_use]
+                fn a(mut self, a: T) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[must_use]
+                fn d(mut self, d: Vec<U>) -> Self {
+                    self.d = d;
+                    self
+                }
+            }
+        }
+        ; "constructor with generics"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(required, default = "1")]
+                a: u32
+            }
+        },
+        &quote! {} => panics
+        ; "required with default"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(each = "arg", into)]
+                a: Vec<String>,
+                #[set(each = "tag")]
+                b: std::collections::BTreeSet<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: impl Into<Vec<String> >) -> Self {
+                    self.a = a.into();
+                    self
+                }
+                #[doc = "Add an element to `a`"]
+                #[must_use]
+                fn arg(mut self, item: impl Into<String>) -> Self {
+                    self.a.push(item.into());
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: std::collections::BTreeSet<u32>) -> Self {
+                    self.b = b;
+                    self
+                }
+                #[doc = "Add an element to `b`"]
+                #[must_use]
+                fn tag(mut self, item: u32) -> Self {
+                    self.b.insert(item);
+                    self
+                }
+            }
+        }
+        ; "each"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(each = "header")]
+                a: HashMap<String, String>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: HashMap<String, String>) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Insert an entry into `a`"]
+                #[must_use]
+                fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+                    self.a.insert(key.into(), value.into());
+                    self
+                }
+            }
+        }
+        ; "each map"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(extend)]
+                a: Vec<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: Vec<u32>) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Add every item of an iterator to `a`"]
+                #[must_use]
+                fn extend_a(mut self, a: impl IntoIterator<Item = impl Into<u32> >) -> Self {
+                    self.a.extend(a.into_iter().map(Into::into));
+                    self
+                }
+                #[doc = "Replace the contents of `a` with the items of an iterator"]
+                #[must_use]
+                fn replace_a(mut self, a: impl IntoIterator<Item = impl Into<u32> >) -> Self {
+                    self.a.clear();
+                    self.a.extend(a.into_iter().map(Into::into));
+                    self
+                }
+            }
+        }
+        ; "extend"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<V> {
+                #[set(each = "entry", extend, generic)]
+                a: BTreeMap<String, V>
+            }
+        },
+        &quote! {
+            impl<V> MyStruct<V> {
+                #[must_use]
+                fn a(mut self, a: BTreeMap<String, V>) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Insert an entry into `a`"]
+                #[must_use]
+                fn entry<K: Into<String>, V0: Into<V> >(mut self, key: K, value: V0) -> Self {
+                    self.a.insert(key.into(), value.into());
+                    self
+                }
+                #[doc = "Add every item of an iterator to `a`"]
+                #[must_use]
+                fn extend_a<I: IntoIterator<Item = (K, V0)>, K: Into<String>, V0: Into<V> >(mut self, a: I) -> Self {
+                    self.a.extend(a.into_iter().map(|(key, value)| (key.into(), value.into())));
+                    self
+                }
+                #[doc = "Replace the contents of `a` with the items of an iterator"]
+                #[must_use]
+                fn replace_a<I: IntoIterator<Item = (K, V0)>, K: Into<String>, V0: Into<V> >(mut self, a: I) -> Self {
+                    self.a.clear();
+                    self.a.extend(a.into_iter().map(|(key, value)| (key.into(), value.into())));
+                    self
+                }
+            }
+        }
+        ; "generic each and extend map"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(extend, generic)]
+                a: Vec<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: Vec<u32>) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Add every item of an iterator to `a`"]
+                #[must_use]
+                fn extend_a<I: IntoIterator<Item = V>, V: Into<u32> >(mut self, a: I) -> Self {
+                    self.a.extend(a.into_iter().map(Into::into));
+                    self
+                }
+                #[doc = "Replace the contents of `a` with the items of an iterator"]
+                #[must_use]
+                fn replace_a<I: IntoIterator<Item = V>, V: Into<u32> >(mut self, a: I) -> Self {
+                    self.a.clear();
+                    self.a.extend(a.into_iter().map(Into::into));
+                    self
+                }
+            }
+        }
+        ; "generic extend"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(strip, each = "arg")]
+                a: Option<Vec<u32>>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: Vec<u32>) -> Self {
+                    self.a = Some(a);
+                    self
+                }
+                #[doc = "Add an element to `a`"]
+                #[must_use]
+                fn arg(mut self, item: u32) -> Self {
+                    self.a.get_or_insert_with(Default::default).push(item);
+                    self
+                }
+            }
+        }
+        ; "each option"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(append = "push_arg")]
+                a: String
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: String) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Append to `a`"]
+                #[must_use]
+                fn push_arg(mut self, value: impl AsRef<str>) -> Self {
+                    self.a.push_str(value.as_ref());
+                    self
+                }
+            }
+        }
+        ; "append"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(append = "push_arg")]
+                a: Vec<u8>
+            }
+        },
+        &quote! {} => panics
+        ; "append not a buffer"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(append = "push_arg", generic)]
+                a: String
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: String) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Append to `a`"]
+                #[must_use]
+                fn push_arg<V: AsRef<str>>(mut self, value: V) -> Self {
+                    self.a.push_str(value.as_ref());
+                    self
+                }
+            }
+        }
+        ; "generic append"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<V> {
+                #[set(strip, try_into)]
+                a: Option<u8>,
+                b: V
+            }
+        },
+        &quote! {
+            impl<V> MyStruct<V> {
+                #[must_use]
+                fn a(mut self, a: u8) -> Self {
+                    self.a = Some(a);
+                    self
+                }
+                #[doc = "Set `a` from a value which may fail to convert"]
+                fn try_a<V0: ::std::convert::TryInto<u8>>(mut self, a: V0) -> Result<Self, V0::Error> {
+                    let a = ::std::convert::TryInto::try_into(a)?;
+                    self.a = Some(a);
+                    Ok(self)
+                }
+            }
+        }
+        ; "try into"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(try_into, generic)]
+                a: u8
+            }
+        },
+        &quote! {} => panics
+        ; "generic without into or append"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<'a> {
+                #[set(wrap, into)]
+                a: Box<String>,
+                #[set(wrap)]
+                b: Cow<'a, str>,
+                #[set(wrap)]
+                c: Option<Box<u32>>
+            }
+        },
+        &quote! {
+            impl<'a> MyStruct<'a> {
+                #[must_use]
+                fn a(self, a: impl Into<String>) -> Self {
+                    self.__set_a(a.into())
+                }
+                #[inline(never)]
+                fn __set_a(mut self, a: String) -> Self {
+                    self.a = ::std::boxed::Box::new(a);
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: <str as ::std::borrow::ToOwned>::Owned) -> Self {
+                    self.b = ::std::borrow::Cow::Owned(b);
+                    self
+                }
+                #[must_use]
+                fn c(mut self, c: u32) -> Self {
+                    self.c = Some(::std::boxed::Box::new(c));
+                    self
+                }
+            }
+        }
+        ; "wrap"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<'a> {
+                #[set]
+                a: Box<dyn Fn(u32) -> bool + Send>,
+                #[set(strip)]
+                b: Option<Arc<dyn Display + 'a>>
+            }
+        },
+        &quote! {
+            impl<'a> MyStruct<'a> {
+                #[must_use]
+                fn a(mut self, a: impl Fn(u32) -> bool + Send + 'static) -> Self {
+                    self.a = ::std::boxed::Box::new(a);
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: impl Display + 'a) -> Self {
+                    self.b = Some(::std::sync::Arc::new(b));
+                    self
+                }
+            }
+        }
+        ; "trait objects"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(raw)]
+                a: Box<dyn Fn(u32) -> bool + Send>,
+                #[set(strip, raw)]
+                b: Option<Arc<dyn Display>>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: Box<dyn Fn(u32) -> bool + Send>) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: Arc<dyn Display>) -> Self {
+                    self.b = Some(b);
+                    self
+                }
+            }
+        }
+        ; "raw trait objects"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(raw)]
+                a: Box<u32>
+            }
+        },
+        &quote! {} => panics
+        ; "raw not a trait object"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(wrap)]
+                a: Option<u32>
+            }
+        },
+        &quote! {} => panics
+        ; "wrap unsupported type"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(wrap, strip)]
+                a: Option<Box<u32>>
+            }
+        },
+        &quote! {} => panics
+        ; "wrap strip"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(interior, into)]
+                a: RefCell<String>,
+                #[set(interior)]
+                b: Mutex<u32>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: impl Into<RefCell<String> >) -> Self {
+                    self.a = a.into();
+                    self
+                }
+                #[doc = "Set `a` through a shared reference"]
+                fn set_a(&self, a: impl Into<String>) -> &Self {
+                    *self.a.borrow_mut() = a.into();
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: Mutex<u32>) -> Self {
+                    self.b = b;
+                    self
+                }
+                #[doc = "Set `b` through a shared reference"]
+                fn set_b(&self, b: u32) -> &Self {
+                    *self.b.lock().unwrap_or_else(::std::sync::PoisonError::into_inner) = b;
+                    self
+                }
+            }
+        }
+        ; "interior"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(ordering = "Release", swap)]
+                a: AtomicBool
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: AtomicBool) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Store a value in `a`"]
+                fn set_a(&self, a: bool) -> &Self {
+                    self.a.store(a, ::std::sync::atomic::Ordering::Release);
+                    self
+                }
+                #[doc = "Store a value in `a`, returning the previous value"]
+                fn swap_a(&self, a: bool) -> bool {
+                    self.a.swap(a, ::std::sync::atomic::Ordering::Release)
+                }
+            }
+        }
+        ; "atomic"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(ordering = "Acquire")]
+                a: AtomicBool
+            }
+        },
+        &quote! {} => panics
+        ; "atomic acquire store"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(units(secs, secs_f64))]
+                timeout: Duration
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn timeout(mut self, timeout: Duration) -> Self {
+                    self.timeout = timeout;
+                    self
+                }
+                #[doc = "Set `timeout` in seconds"]
+                #[must_use]
+                fn timeout_secs(mut self, timeout: u64) -> Self {
+                    self.timeout = ::std::time::Duration::from_secs(timeout);
+                    self
+                }
+                #[doc = "Set `timeout` in fractional seconds"]
+                #[must_use]
+                fn timeout_secs_f64(mut self, timeout: f64) -> Self {
+                    self.timeout = ::std::time::Duration::from_secs_f64(timeout);
+                    self
+                }
+            }
+        }
+        ; "units"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(args(width, height), into)]
+                size: (u32, u32),
+                #[set(destructure)]
+                range: (f64, f64),
+                #[set]
+                point: (u8, u8)
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn size(mut self, width: impl Into<u32>, height: impl Into<u32>) -> Self {
+                    self.size = (width.into(), height.into(),);
+                    self
+                }
+                #[must_use]
+                fn range(mut self, range_0: f64, range_1: f64) -> Self {
+                    self.range = (range_0, range_1,);
+                    self
+                }
+                #[must_use]
+                fn point(mut self, point: (u8, u8)) -> Self {
+                    self.point = point;
+                    self
+                }
+            }
+        }
+        ; "tuple"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(args(width))]
+                size: (u32, u32)
+            }
+        },
+        &quote! {} => panics
+        ; "tuple args count"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(destructure)]
+                size: [u32; 2]
+            }
+        },
+        &quote! {} => panics
+        ; "destructure not a tuple"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<const N: usize> {
+                #[set(index(fill, at_const))]
+                a: [u8; N]
+            }
+        },
+        &quote! {
+            impl<const N: usize> MyStruct<N> {
+                #[must_use]
+                fn a(mut self, a: [u8; N]) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Set the element of `a` at an index\n\n# Panics\n\nPanics if the index is out of bounds"]
+                #[must_use]
+                fn a_at(mut self, index: usize, value: u8) -> Self {
+                    self.a[index] = value;
+                    self
+                }
+                #[doc = "Set every element of `a` to a value"]
+                #[must_use]
+                fn a_fill(mut self, value: u8) -> Self where u8: Clone {
+                    for element in self.a.iter_mut() {
+                        *element = value.clone();
+                    }
+                    self
+                }
+                #[doc = "Set the element of `a` at an index which is checked at compile time\n\nRequires Rust 1.57 or later"]
+                #[must_use]
+                fn a_at_const<const INDEX: usize>(mut self, value: u8) -> Self {
+                    struct InBounds<const INDEX: usize, const LEN: usize>;
+                    impl<const INDEX: usize, const LEN: usize> InBounds<INDEX, LEN> {
+                        const CHECK: () = assert!(INDEX < LEN, "index out of bounds");
+                    }
+                    let () = InBounds::<INDEX, N>::CHECK;
+
+                    self.a[INDEX] = value;
+                    self
+                }
+            }
+        }
+        ; "index"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(index(at_const))]
+                a: [u8; 2]
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: [u8; 2]) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Set the element of `a` at an index\n\n# Panics\n\nPanics if the index is out of bounds"]
+                #[must_use]
+                fn a_at(mut self, index: usize, value: u8) -> Self {
+                    self.a[index] = value;
+                    self
+                }
+                #[doc = "Set the element of `a` at an index which is checked at compile time\n\nRequires Rust 1.57 or later"]
+                #[must_use]
+                fn a_at_const<const INDEX: usize>(mut self, value: u8) -> Self {
+                    struct InBounds<const INDEX: usize, const LEN: usize>;
+                    impl<const INDEX: usize, const LEN: usize> InBounds<INDEX, LEN> {
+                        const CHECK: () = assert!(INDEX < LEN, "index out of bounds");
+                    }
+                    let () = InBounds::<INDEX, 2>::CHECK;
+
+                    self.a[INDEX] = value;
+                    self
+                }
+            }
+        }
+        ; "index literal length"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(index)]
+                a: [Vec<u8>; 2]
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: [Vec<u8>; 2]) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Set the element of `a` at an index\n\n# Panics\n\nPanics if the index is out of bounds"]
+                #[must_use]
+                fn a_at(mut self, index: usize, value: Vec<u8>) -> Self {
+                    self.a[index] = value;
+                    self
+                }
+            }
+        }
+        ; "index without optional setters"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(index(at_const))]
+                a: [u8; LEN + 1]
+            }
+        },
+        &quote! {} => panics
+        ; "at const computed length"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(strip, flag, negate = "no_colour")]
+                colour: bool,
+                #[set(flag)]
+                verbose: Option<bool>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn colour(mut self) -> Self {
+                    self.colour = true;
+                    self
+                }
+                #[doc = "Set `colour` to `true`"]
+                #[must_use]
+                fn enable_colour(mut self) -> Self {
+                    self.colour = true;
+                    self
+                }
+                #[doc = "Set `colour` to `false`"]
+                #[must_use]
+                fn no_colour(mut self) -> Self {
+                    self.colour = false;
+                    self
+                }
+                #[doc = "Set `colour` to the value of a condition"]
+                #[must_use]
+                fn colour_if(mut self, cond: bool) -> Self {
+                    self.colour = cond;
+                    self
+                }
+                #[must_use]
+                fn verbose(mut self, verbose: Option<bool>) -> Self {
+                    self.verbose = verbose;
+                    self
+                }
+                #[doc = "Set `verbose` to `true`"]
+                #[must_use]
+                fn enable_verbose(mut self) -> Self {
+                    self.verbose = Some(true);
+                    self
+                }
+                #[doc = "Set `verbose` to `false`"]
+                #[must_use]
+                fn disable_verbose(mut self) -> Self {
+                    self.verbose = Some(false);
+                    self
+                }
+                #[doc = "Set `verbose` to the value of a condition"]
+                #[must_use]
+                fn verbose_if(mut self, cond: bool) -> Self {
+                    self.verbose = Some(cond);
+                    self
+                }
+                #[doc = "Set `verbose` to `None`"]
+                #[must_use]
+                fn unset_verbose(mut self) -> Self {
+                    self.verbose = None;
+                    self
+                }
+            }
+        }
+        ; "flag"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(strip, into, conditional)]
+                a: Option<String>,
+                #[set(destructure, conditional)]
+                b: (u8, u16),
+                #[set(strip, conditional)]
+                c: bool
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: impl Into<String>) -> Self {
+                    self.a = Some(a.into());
+                    self
+                }
+                #[doc = "Set `a` if a condition holds"]
+                #[must_use]
+                fn a_if(self, cond: bool, a: impl Into<String>) -> Self {
+                    if cond {
+                        self.a(a)
+                    } else {
+                        self
+                    }
+                }
+                #[doc = "Set `a` if a value is given"]
+                #[must_use]
+                fn a_some(self, a: Option<impl Into<String> >) -> Self {
+                    match a {
+                        Some(a) => self.a(a),
+                        None => self,
+                    }
+                }
+                #[must_use]
+                fn b(mut self, b_0: u8, b_1: u16) -> Self {
+                    self.b = (b_0, b_1,);
+                    self
+                }
+                #[doc = "Set `b` if a condition holds"]
+                #[must_use]
+                fn b_if(self, cond: bool, b_0: u8, b_1: u16) -> Self {
+                    if cond {
+                        self.b(b_0, b_1)
+                    } else {
+                        self
+                    }
+                }
+                #[doc = "Set `b` if a value is given"]
+                #[must_use]
+                fn b_some(self, b: Option<(u8, u16)>) -> Self {
+                    match b {
+                        Some((b_0, b_1)) => self.b(b_0, b_1),
+                        None => self,
+                    }
+                }
+                #[must_use]
+                fn c(mut self) -> Self {
+                    self.c = true;
+                    self
+                }
+                #[doc = "Set `c` if a condition holds"]
+                #[must_use]
+                fn c_if(self, cond: bool,) -> Self {
+                    if cond {
+                        self.c()
+                    } else {
+                        self
+                    }
+                }
+            }
+        }
+        ; "conditional"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(flag, conditional)]
+                a: bool
+            }
+        },
+        &quote! {} => panics
+        ; "conditional with flag"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(each = "a")]
+                a: Vec<u32>
+            }
+        },
+        &quote! {} => panics
+        ; "each same name as field"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(each = "arg")]
+                a: u32
+            }
+        },
+        &quote! {} => panics
+        ; "each not a collection"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(strip, into, clear, maybe)]
+                a: Option<String>
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: impl Into<String>) -> Self {
+                    self.a = Some(a.into());
+                    self
+                }
+                #[doc = "Set `a` to `None`"]
+                #[must_use]
+                fn clear_a(mut self) -> Self {
+                    self.a = None;
+                    self
+                }
+                #[doc = "Set `a` from an `Option`"]
+                #[must_use]
+                fn maybe_a(mut self, a: Option<impl Into<String> >) -> Self {
+                    self.a = a.map(Into::into);
+                    self
+                }
+            }
+        }
+        ; "clear and maybe"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(maybe)]
+                a: Option<u32>
+            }
+        },
+        &quote! {} => panics
+        ; "maybe without strip"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(reset, default = "8080")]
+                a: u16,
+                #[set(reset, into)]
+                b: String
+            }
+        },
+        &quote! {
+            impl MyStruct {
+                #[must_use]
+                fn a(mut self, a: u16) -> Self {
+                    self.a = a;
+                    self
+                }
+                #[doc = "Reset `a` to its default value"]
+                #[must_use]
+                fn reset_a(mut self) -> Self {
+                    self.a = 8080;
+                    self
+                }
+                #[must_use]
+                fn b(mut self, b: impl Into<String>) -> Self {
+                    self.b = b.into();
+                    self
+                }
+                #[doc = "Reset `b` to its default value"]
+                #[must_use]
+                fn reset_b(mut self) -> Self {
+                    self.b = Default::default();
+                    self
+                }
+            }
+        }
+        ; "reset"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(required, reset)]
+                a: u16
+            }
+        },
+        &quote! {} => panics
+        ; "required reset"
+    )]
+    #[test_case(
+        quote! {
+            #[set(impl_default)]
+            struct MyStruct<T> {
+                #[set(default = "8080")]
+                a: u16,
+                b: T
+            }
+        },
+        &quote! {
+            impl<T> MyStruct<T> {
+                #[must_use]
+                fn a(mut self, a: u16) -> Self {
+                    self.a = a;
+                    self
+                }
+            }
+
+            impl<T> Default for MyStruct<T> where T: Default {
+                fn default() -> Self {
+                    Self {
+                        a: 8080,
+                        b: Default::default(),
+                    }
+                }
+            }
+        }
+        ; "impl default"
+    )]
+    #[test_case(
+        quote! {
+            #[set(impl_default)]
+            struct MyStruct {
+                a: u16
+            }
+        },
+        &quote! {
+            impl Default for MyStruct {
+                fn default() -> Self {
+                    Self {
+                        a: Default::default(),
+                    }
+                }
+            }
+        }
+        ; "impl default without setters"
+    )]
+    #[test_case(
+        quote! {
+            #[set(into)]
+            struct MyStruct {
+                #[set]
+                a: u16
+            }
+        },
+        &quote! {} => panics
+        ; "field argument on struct"
+    )]
+    #[test_case(
+        quote! {
+            #[set(impl_defualt)]
+            struct MyStruct {
+                a: u16
+            }
+        },
+        &quote! {} => panics
+        ; "unrecognised struct argument"
+    )]
+    #[test_case(
+        quote! {
+            #[set(typestate)]
+            struct MyStruct {
+                #[set]
+                a: u16
+            }
+        },
+        &quote! {} => panics
+        ; "typestate without builder"
+    )]
+    fn parse(input: TokenStream2, expected: &TokenStream2) {
+        let parser = DeriveInput::parse;
+        let derive_input = parser.parse2(input).unwrap();
+
+        let data = Data::from_derive_input(derive_input);
+
+        assert_eq!(data.generate_impl().to_string(), expected.to_string());
+    }
+
+    /// The `into` setters generated for a struct with many fields of type
+    /// `ty`, using the given arguments.
+    fn into_setters(args: &TokenStream2, ty: &TokenStream2) -> TokenStream2 {
+        let fields = code_size_fields();
+        let input = quote! {
+            pub struct MyStruct {
+                #(
+                    #[set(pub, #args)]
+                    #fields: #ty,
+                )*
+            }
+        };
+        Data::from_derive_input(DeriveInput::parse.parse2(input).unwrap()).generate_impl()
+    }
+
+    fn code_size_fields() -> Vec<Ident> {
+        (0..16_usize).map(|i| format_ident!("a{}", i)).collect()
+    }
+
+    /// Compile a struct with many fields of type `ty` and the given setters,
+    /// call each setter with several `str`-like input types, and return the
+    /// size of the compiled object file.
+    fn into_code_size(
+        name: &str,
+        setters: &TokenStream2,
+        ty: &TokenStream2,
+        opt_level: &str,
+    ) -> u64 {
+        let fields = code_size_fields();
+        let source = quote! {
+            pub struct MyStruct {
+                #(#fields: #ty,)*
+            }
+
+            #setters
+
+            pub fn use_setters(s: MyStruct) -> MyStruct {
+                s #(
+                    .#fields("a")
+                    .#fields(String::new())
+                    .#fields('c')
+                    .#fields(Box::<str>::from("b"))
+                    .#fields(std::borrow::Cow::Borrowed("c"))
+                )*
+            }
+        };
+
+        let dir = std::env::temp_dir().join(format!(
+            "fluent-setters-code-size-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let src = dir.join("lib.rs");
+        let obj = dir.join(format!("lib-{}.o", opt_level));
+        std::fs::write(&src, source.to_string()).unwrap();
+
+        let status = std::process::Command::new(
+            std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()),
+        )
+        .args(&["--edition", "2018", "--crate-type", "lib", "--emit", "obj"])
+        .arg(format!("-Copt-level={}", opt_level))
+        .arg("-o")
+        .arg(&obj)
+        .arg(&src)
+        .status()
+        .unwrap();
+        assert!(status.success());
+
+        let size = std::fs::metadata(&obj).unwrap().len();
+        std::fs::remove_dir_all(&dir).unwrap();
+        size
+    }
+
+    /// Compare the compiled code size of `into` setters with and without
+    /// outlining, both for setters which only store the value and for `wrap`
+    /// setters which also allocate, which are outlined by default. Outlining
+    /// only pays off for the latter, and only once the compiler inlines.
+    ///
+    /// run with `cargo test -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark which invokes rustc"]
+    fn bench_into_code_size() {
+        let fields = code_size_fields();
+        let store = into_setters(&quote!(into), &quote!(String));
+        let store_outlined = into_setters(&quote!(into, outline), &quote!(String));
+        // what `wrap` setters would be without outlining
+        let wrap_inline = quote! {
+            impl MyStruct {
+                #(
+                    pub fn #fields(mut self, #fields: impl Into<String>) -> Self {
+                        self.#fields = ::std::boxed::Box::new(#fields.into());
+                        self
+                    }
+                )*
+            }
+        };
+        let wrap = into_setters(&quote!(into, wrap), &quote!(Box<String>));
+
+        for opt_level in &["0", "3", "s"] {
+            let generic = into_code_size("store", &store, &quote!(String), opt_level);
+            let outlined = into_code_size(
+                "store_outlined",
+                &store_outlined,
+                &quote!(String),
+                opt_level,
+            );
+            println!(
+                "opt-level={}: store: generic {} bytes, outlined {} bytes",
+                opt_level, generic, outlined
+            );
+
+            let generic =
+                into_code_size("wrap_inline", &wrap_inline, &quote!(Box<String>), opt_level);
+            let outlined = into_code_size("wrap", &wrap, &quote!(Box<String>), opt_level);
+            println!(
+                "opt-level={}: wrap: generic {} bytes, outlined {} bytes",
+                opt_level, generic, outlined
+            );
+            if *opt_level != "0" {
+                assert!(
+                    outlined < generic,
+                    "outlining didn't shrink the wrap setters at opt-level={}",
+                    opt_level
+                );
+            }
+        }
     }
 }
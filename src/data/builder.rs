@@ -0,0 +1,271 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+use super::Data;
+use crate::field::{Context, Field};
+
+impl Data {
+    /// Generate a separate builder type for the struct, which stores each
+    /// field in an `Option` and checks at runtime that the required fields
+    /// have been set.
+    pub fn generate_builder(&self) -> TokenStream2 {
+        assert!(
+            !self.args.impl_default,
+            "'impl_default' argument is only valid for `FluentSetters`"
+        );
+        if self.args.typestate {
+            return self.generate_typestate_builder();
+        }
+
+        let vis = &self.vis;
+        let name = &self.name;
+        let builder = format_ident!("{}Builder", name);
+        let error = format_ident!("{}BuildError", name);
+
+        let context = Context {
+            name: &builder,
+            generics: &self.generics,
+            fields: &self.members,
+            attrs: &self.args.attrs,
+            builder: true,
+            transition: None,
+        };
+        let setters: TokenStream2 = self
+            .fields
+            .iter()
+            .map(|field| field.generate_setter(&context))
+            .collect();
+
+        let members: Vec<_> = self.members.iter().map(|(member, _)| member).collect();
+        let types = self.members.iter().map(|(_, ty)| ty);
+        let required: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|field| field.is_required())
+            .map(Field::name)
+            .collect();
+        let (optional, build_where_clause) = self.optional_inits(&required);
+
+        let generics = &self.generics;
+        let struct_where_clause = &generics.where_clause;
+        let bounded_generics = self.bounded_generics();
+        let (impl_generics, ty_generics, where_clause) = bounded_generics.split_for_impl();
+
+        let builder_doc = format!("A builder for [`{}`]", name);
+        let error_doc = format!(
+            "The error returned when building a [`{}`] without setting all of its required fields",
+            name
+        );
+
+        quote! {
+            #[doc = #builder_doc]
+            #vis struct #builder #generics #struct_where_clause {
+                #(#members: Option<#types>,)*
+            }
+
+            impl #impl_generics Default for #builder #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#members: None,)*
+                    }
+                }
+            }
+
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Create a builder for this type
+                #vis fn builder() -> #builder #ty_generics {
+                    #builder::default()
+                }
+            }
+
+            impl #impl_generics #builder #ty_generics #where_clause {
+                #setters
+
+                /// Build the value, filling any fields which haven't been set
+                /// from their defaults.
+                ///
+                /// Fails if any required fields haven't been set.
+                #vis fn build(self) -> Result<#name #ty_generics, #error> #build_where_clause {
+                    let mut missing = Vec::new();
+                    #(
+                        if self.#required.is_none() {
+                            missing.push(stringify!(#required));
+                        }
+                    )*
+                    if !missing.is_empty() {
+                        return Err(#error { missing });
+                    }
+
+                    Ok(#name {
+                        #(#required: self.#required.unwrap(),)*
+                        #(#optional,)*
+                    })
+                }
+            }
+
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #vis struct #error {
+                missing: Vec<&'static str>,
+            }
+
+            impl #error {
+                /// The names of the required fields which weren't set
+                #vis fn missing_fields(&self) -> &[&'static str] {
+                    &self.missing
+                }
+            }
+
+            impl ::std::fmt::Display for #error {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    write!(f, "missing required fields: {}", self.missing.join(", "))
+                }
+            }
+
+            impl ::std::error::Error for #error {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Data;
+    use proc_macro2::TokenStream as TokenStream2;
+    use quote::quote;
+    use syn::{
+        parse::{Parse, Parser},
+        DeriveInput,
+    };
+    use test_case::test_case;
+
+    #[test_case(
+        quote! {
+            pub struct MyStruct {
+                #[set(required)]
+                a: u32,
+                #[set(strip)]
+                b: bool,
+                c: u32
+            }
+        },
+        &quote! {
+            #[doc = "A builder for [`MyStruct`]"]
+            pub struct MyStructBuilder {
+                a: Option<u32>,
+                b: Option<bool>,
+                c: Option<u32>,
+            }
+
+            impl Default for MyStructBuilder {
+                fn default() -> Self {
+                    Self {
+                        a: None,
+                        b: None,
+                        c: None,
+                    }
+                }
+            }
+
+            impl MyStruct {
+                /// Create a builder for this type
+                pub fn builder() -> MyStructBuilder {
+                    MyStructBuilder::default()
+                }
+            }
+
+            impl MyStructBuilder {
+                #[must_use]
+                fn a(mut self, a: u32) -> Self {
+                    self.a = Some(a);
+                    self
+                }
+                #[must_use]
+                fn b(mut self) -> Self {
+                    self.b = Some(true);
+                    self
+                }
+
+                /// Build the value, filling any fields which haven't been set
+                /// from their defaults.
+                ///
+                /// Fails if any required fields haven't been set.
+                pub fn build(self) -> Result<MyStruct, MyStructBuildError> {
+                    let mut missing = Vec::new();
+                    if self.a.is_none() {
+                        missing.push(stringify!(a));
+                    }
+                    if !missing.is_empty() {
+                        return Err(MyStructBuildError { missing });
+                    }
+
+                    Ok(MyStruct {
+                        a: self.a.unwrap(),
+                        b: self.b.unwrap_or_default(),
+                        c: self.c.unwrap_or_default(),
+                    })
+                }
+            }
+
+            #[doc = "The error returned when building a [`MyStruct`] without setting all of its required fields"]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct MyStructBuildError {
+                missing: Vec<&'static str>,
+            }
+
+            impl MyStructBuildError {
+                /// The names of the required fields which weren't set
+                pub fn missing_fields(&self) -> &[&'static str] {
+                    &self.missing
+                }
+            }
+
+            impl ::std::fmt::Display for MyStructBuildError {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    write!(f, "missing required fields: {}", self.missing.join(", "))
+                }
+            }
+
+            impl ::std::error::Error for MyStructBuildError {}
+        }
+        ; "basic"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct<T> {
+                #[set(retype)]
+                a: T
+            }
+        },
+        &quote! {} => panics
+        ; "retype"
+    )]
+    #[test_case(
+        quote! {
+            #[set(impl_default)]
+            struct MyStruct {
+                #[set]
+                a: u32
+            }
+        },
+        &quote! {} => panics
+        ; "impl default"
+    )]
+    #[test_case(
+        quote! {
+            struct MyStruct {
+                #[set(swap)]
+                a: AtomicBool
+            }
+        },
+        &quote! {} => panics
+        ; "atomic swap"
+    )]
+    fn builder(input: TokenStream2, expected: &TokenStream2) {
+        let parser = DeriveInput::parse;
+        let derive_input = parser.parse2(input).unwrap();
+
+        let data = Data::from_derive_input(derive_input);
+
+        assert_eq!(data.generate_builder().to_string(), expected.to_string());
+    }
+}
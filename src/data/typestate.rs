@@ -0,0 +1,267 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{GenericParam, Generics, Ident, Visibility};
+
+use super::Data;
+use crate::field::{Context, Field, Transition};
+
+impl Data {
+    /// Generate a builder whose type parameters track which of the required
+    /// fields have been set, so that `build` can only be called once all of
+    /// them have been provided.
+    pub(super) fn generate_typestate_builder(&self) -> TokenStream2 {
+        let vis = &self.vis;
+        let name = &self.name;
+        let builder = format_ident!("{}Builder", name);
+        let required = Required::new(self, &builder);
+        let Required {
+            fields: required_fields,
+            states,
+            missing,
+            set,
+        } = &required;
+
+        let builder_ty = |states: &[Ident]| builder_ty(&builder, &self.generics, states);
+        let states_ty = builder_ty(states);
+        let missing_ty = builder_ty(missing);
+        let set_ty = builder_ty(set);
+        let next_states: Vec<_> = states
+            .iter()
+            .map(|state| format_ident!("{}Next", state))
+            .collect();
+        let next_ty = builder_ty(&next_states);
+
+        let mut builder_generics = self.generics.clone();
+        builder_generics.params.extend(required.params());
+        let builder_where_clause = &self.generics.where_clause;
+        let mut bounded_builder_generics = self.bounded_generics();
+        bounded_builder_generics.params.extend(required.params());
+        let (impl_generics, _, where_clause) = bounded_builder_generics.split_for_impl();
+
+        let bounded_generics = self.bounded_generics();
+        let (struct_impl_generics, struct_ty_generics, struct_where_clause) =
+            bounded_generics.split_for_impl();
+
+        let setters =
+            self.generate_typestate_setters(&builder, &bounded_builder_generics, &required);
+        let markers = required.markers(vis, &builder);
+
+        let members: Vec<_> = self.members.iter().map(|(member, _)| member).collect();
+        let types = self.members.iter().map(|(_, ty)| ty);
+        let (optional, build_where_clause) = self.optional_inits(required_fields);
+
+        let builder_doc = format!(
+            "A builder for [`{}`], which can only be built once all of the required fields have \
+             been set",
+            name
+        );
+
+        quote! {
+            #markers
+
+            #[doc = #builder_doc]
+            #vis struct #builder #builder_generics #builder_where_clause {
+                #(#members: Option<#types>,)*
+                __state: ::std::marker::PhantomData<(#(#states,)*)>,
+            }
+
+            impl #struct_impl_generics Default for #missing_ty #struct_where_clause {
+                fn default() -> Self {
+                    #builder {
+                        #(#members: None,)*
+                        __state: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #struct_impl_generics #name #struct_ty_generics #struct_where_clause {
+                /// Create a builder for this type
+                #vis fn builder() -> #missing_ty {
+                    Default::default()
+                }
+            }
+
+            impl #impl_generics #states_ty #where_clause {
+                #setters
+
+                fn __transition<#(#next_states),*>(self) -> #next_ty {
+                    #builder {
+                        #(#members: self.#members,)*
+                        __state: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #struct_impl_generics #set_ty #struct_where_clause {
+                /// Build the value, filling any fields which haven't been set
+                /// from their defaults.
+                #vis fn build(self) -> #name #struct_ty_generics #build_where_clause {
+                    #name {
+                        #(#required_fields: self.#required_fields.unwrap(),)*
+                        #(#optional,)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generate the setters of a typestate builder, where setting a required
+    /// field marks it as set in the type of the builder.
+    fn generate_typestate_setters(
+        &self,
+        builder: &Ident,
+        generics: &Generics,
+        required: &Required,
+    ) -> TokenStream2 {
+        let context = Context {
+            name: builder,
+            generics,
+            fields: &self.members,
+            attrs: &self.args.attrs,
+            builder: true,
+            transition: None,
+        };
+
+        self.fields
+            .iter()
+            .map(|field| {
+                let transition = required
+                    .fields
+                    .iter()
+                    .position(|required| *required == field.name())
+                    .map(|i| {
+                        let mut states = required.states.clone();
+                        states[i] = required.set[i].clone();
+                        Transition {
+                            ty: builder_ty(builder, &self.generics, &states),
+                            expr: quote!(self.__transition()),
+                        }
+                    });
+                field.generate_setter(&Context {
+                    transition: transition.as_ref(),
+                    ..context
+                })
+            })
+            .collect()
+    }
+}
+
+/// The names generated for the required fields of a typestate builder
+struct Required<'a> {
+    fields: Vec<&'a Ident>,
+
+    /// The type parameters of the builder which track whether each field has
+    /// been set
+    states: Vec<Ident>,
+
+    /// The marker types for fields which haven't been set
+    missing: Vec<Ident>,
+
+    /// The marker types for fields which have been set
+    set: Vec<Ident>,
+}
+
+impl<'a> Required<'a> {
+    fn new(data: &'a Data, builder: &Ident) -> Self {
+        let fields: Vec<_> = data
+            .fields
+            .iter()
+            .filter(|field| field.is_required())
+            .map(Field::name)
+            .collect();
+        let names = |suffix: &str| {
+            fields
+                .iter()
+                .map(|field| format_ident!("{}{}{}", builder, camel_case(field), suffix))
+                .collect()
+        };
+        let states = fields
+            .iter()
+            .map(|field| format_ident!("{}State", camel_case(field)))
+            .collect();
+
+        Self {
+            states,
+            missing: names("Missing"),
+            set: names("Set"),
+            fields,
+        }
+    }
+
+    /// The type parameters of the builder which track whether each field has
+    /// been set.
+    fn params(&self) -> impl Iterator<Item = GenericParam> + '_ {
+        self.states
+            .iter()
+            .map(|state| -> GenericParam { syn::parse_quote!(#state) })
+    }
+
+    /// Generate the marker types for each field.
+    fn markers(&self, vis: &Visibility, builder: &Ident) -> TokenStream2 {
+        let missing = &self.missing;
+        let set = &self.set;
+        let missing_docs = self.fields.iter().map(|field| {
+            format!(
+                "Marks that the required `{}` field of a [`{}`] hasn't been set",
+                field, builder
+            )
+        });
+        let set_docs = self.fields.iter().map(|field| {
+            format!(
+                "Marks that the required `{}` field of a [`{}`] has been set",
+                field, builder
+            )
+        });
+
+        quote! {
+            #(
+                #[doc = #missing_docs]
+                #vis struct #missing;
+
+                #[doc = #set_docs]
+                #vis struct #set;
+            )*
+        }
+    }
+}
+
+/// The type of the builder, with the given states for each of its required
+/// fields.
+fn builder_ty(builder: &Ident, generics: &Generics, states: &[Ident]) -> TokenStream2 {
+    let args = generics.params.iter().map(|param| match param {
+        GenericParam::Type(ty) => ty.ident.to_token_stream(),
+        GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_token_stream(),
+        GenericParam::Const(constant) => constant.ident.to_token_stream(),
+    });
+    quote!(#builder<#(#args,)* #(#states),*>)
+}
+
+/// Convert a `snake_case` field name to `CamelCase`.
+fn camel_case(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    ident
+        .trim_start_matches("r#")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::camel_case;
+    use syn::{ext::IdentExt, parse::Parser, Ident};
+    use test_case::test_case;
+
+    #[test_case("name" => "Name")]
+    #[test_case("retry_count" => "RetryCount")]
+    #[test_case("r#type" => "Type")]
+    fn field_name_to_camel_case(input: &str) -> String {
+        let parser = Ident::parse_any;
+        camel_case(&parser.parse_str(input).unwrap())
+    }
+}
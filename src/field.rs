@@ -1,14 +1,58 @@
+pub use args::Args;
 pub use attributes::Attributes;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::Ident;
+use quote::{format_ident, quote, ToTokens};
+pub use retype::contains_ident;
+use syn::{Expr, Generics, Ident, Meta, Token, WhereClause};
 
+mod append;
 mod args;
+mod array;
 mod attributes;
+mod collection;
+mod conditional;
+mod duration;
+mod flag;
+mod interior;
+mod retype;
+mod try_into;
+mod tuple;
 mod ty;
 mod visibility;
+mod wrap;
 
 use ty::Type;
+use visibility::Visibility;
+
+/// The struct which setters are being generated for
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    pub name: &'a Ident,
+    pub generics: &'a Generics,
+
+    /// The names and types of all of the struct's fields, including those
+    /// without setters
+    pub fields: &'a [(Ident, syn::Type)],
+
+    /// The attributes forwarded from the struct-level `#[set(attr(...))]`
+    /// argument
+    pub attrs: &'a [Meta],
+
+    /// Whether the setters are for a builder, which stores each field in an
+    /// `Option`
+    pub builder: bool,
+
+    /// Overrides the return type of the setters, for setters which change the
+    /// type of a typestate builder
+    pub transition: Option<&'a Transition>,
+}
+
+/// The return type and final expression of a setter which changes the type
+/// of the value it is called on
+pub struct Transition {
+    pub ty: TokenStream2,
+    pub expr: TokenStream2,
+}
 
 pub struct Field {
     name: Ident,
@@ -27,64 +71,469 @@ impl Field {
         Some(Self { name, ty, attrs })
     }
 
-    pub fn generate_setter(&self) -> TokenStream2 {
-        let doc = &self.attrs.doc_attribute();
-        let visibility = &self.attrs.visibility;
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.attrs.required
+    }
+
+    /// The expression from the `#[set(default = "...")]` argument, if any
+    pub fn default_expr(&self) -> Option<&Expr> {
+        self.attrs.default.as_ref()
+    }
+
+    /// Generate the constructor parameter for a required field.
+    pub fn generate_param(&self) -> TokenStream2 {
         let field = &self.name;
         let ty = &self.ty;
+        if self.attrs.into {
+            quote!(#field: impl Into<#ty>)
+        } else {
+            quote!(#field: #ty)
+        }
+    }
 
-        match (self.attrs.into, self.attrs.strip, &self.ty) {
-            (true, true, Type::Bool(_)) => panic!("can't use both 'strip' and 'into' on a bool"),
-            (true, true, Type::Option(option)) => {
-                let inner_ty = option.inner_ty();
-                quote! {
-                    #doc
-                    #visibility fn #field(mut self, #field: impl Into<#inner_ty>) -> Self {
-                        self.#field = Some(#field.into());
-                        self
-                    }
-                }
+    /// Generate the initialiser of a required field from its constructor
+    /// parameter.
+    pub fn generate_init(&self) -> TokenStream2 {
+        let field = &self.name;
+        if self.attrs.into {
+            quote!(#field: #field.into())
+        } else {
+            quote!(#field)
+        }
+    }
+
+    /// Check for combinations of arguments which can't be used together.
+    fn validate(&self, context: &Context) {
+        assert!(
+            !(self.attrs.constant && self.attrs.into),
+            "can't use both 'const' and 'into' on a setter"
+        );
+        assert!(
+            !(self.attrs.constant && self.attrs.try_into),
+            "can't use both 'const' and 'try_into' on a setter"
+        );
+        assert!(
+            !(self.attrs.constant && self.ty.needs_drop()),
+            "'const' setters can't drop the previous value of `{}`, so its type must not need \
+             dropping",
+            self.name
+        );
+        assert!(
+            !self.attrs.outline || self.attrs.into,
+            "'outline' argument is only valid together with 'into'"
+        );
+        assert!(
+            !self.attrs.generic
+                || self.attrs.into
+                || self.attrs.append.is_some()
+                || self.converts_items(context),
+            "'generic' argument is only valid together with 'into', 'append', 'extend' or a map's 'each'"
+        );
+        assert!(
+            !(self.attrs.constant && context.transition.is_some()),
+            "'const' is not supported for typestate builders"
+        );
+        assert!(
+            !(self.attrs.required && self.attrs.default.is_some()),
+            "'required' fields can't have a 'default'"
+        );
+        assert!(
+            !(self.attrs.wrap && self.attrs.strip),
+            "can't use both 'wrap' and 'strip' on a setter"
+        );
+        assert!(
+            !(self.attrs.wrap && self.attrs.raw),
+            "can't use both 'wrap' and 'raw' on a setter"
+        );
+        assert!(
+            !(self.attrs.wrap && self.attrs.constant),
+            "can't use both 'wrap' and 'const' on a setter"
+        );
+        assert!(
+            !self.destructures() || matches!(self.ty, Type::Tuple(_)),
+            "'destructure' and 'args' arguments are only valid for tuple fields"
+        );
+        assert!(
+            !(self.attrs.required && self.attrs.reset),
+            "'required' fields can't be reset"
+        );
+    }
+
+    /// Generate the setters for this field.
+    pub fn generate_setter(&self, context: &Context) -> TokenStream2 {
+        let common = Common::new(self, context);
+
+        if self.attrs.retype {
+            assert!(!context.builder, "'retype' is not supported for builders");
+            return self.generate_retype_setter(context, &common.attrs);
+        }
+
+        self.validate(context);
+
+        let setter = match &self.ty {
+            Type::Tuple(tuple) if self.destructures() => {
+                self.generate_tuple_setter(context, &common, tuple)
             }
-            (_, true, Type::Other(_)) => {
-                panic!("'strip' argument is only valid for `Option` and `bool` fields")
+            _ => self.generate_value_setter(context, &common),
+        };
+        let option = self.generate_option_setters(context, &common);
+        let flag = self.generate_flag_setters(context, &common);
+        let each = self.generate_each_setter(context, &common);
+        let extend = self.generate_extend_setters(context, &common);
+        let append = self.generate_append_setter(context, &common);
+        let interior = self.generate_interior_setter(context, &common);
+        let units = self.generate_unit_setters(context, &common);
+        let index = self.generate_index_setters(context, &common);
+        let reset = self.generate_reset_setter(context, &common);
+        let conditional = self.generate_conditional_setters(context, &common);
+        let try_into = self.generate_try_into_setter(context, &common);
+
+        quote! {
+            #setter
+            #option
+            #flag
+            #each
+            #extend
+            #append
+            #interior
+            #units
+            #index
+            #reset
+            #conditional
+            #try_into
+        }
+    }
+
+    /// Generate the `clear_` and `maybe_` setters of a stripped `Option` field,
+    /// if the `clear` or `maybe` arguments are present.
+    fn generate_option_setters(&self, context: &Context, common: &Common) -> Option<TokenStream2> {
+        if !(self.attrs.clear || self.attrs.maybe) {
+            return None;
+        }
+
+        let inner_ty = match &self.ty {
+            Type::Option(option) if self.attrs.strip => option.inner_ty(),
+            _ => {
+                panic!("'clear' and 'maybe' arguments are only valid for stripped `Option` fields")
             }
-            (true, false, _) => {
-                quote! {
-                    #doc
-                    #visibility fn #field(mut self, #field: impl Into<#ty>) -> Self {
-                        self.#field = #field.into();
-                        self
-                    }
+        };
+
+        let Common {
+            forwarded,
+            visibility,
+            constness,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+
+        let clear = if self.attrs.clear {
+            let clear = format_ident!("clear_{}", field);
+            let doc = format!("Set `{}` to `None`", field);
+            let assignment = self.store(context, &quote!(None));
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility #constness fn #clear(mut self) -> #output #where_clause {
+                    #assignment
+                    #tail
+                }
+            })
+        } else {
+            None
+        };
+
+        let maybe = if self.attrs.maybe {
+            let maybe = format_ident!("maybe_{}", field);
+            let doc = format!("Set `{}` from an `Option`", field);
+            let (generics, arg_ty) = self.param_ty(context, inner_ty);
+            let value = if self.attrs.into {
+                quote!(#field.map(Into::into))
+            } else {
+                quote!(#field)
+            };
+            let assignment = self.store(context, &value);
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility #constness fn #maybe #generics(mut self, #field: Option<#arg_ty>) -> #output #where_clause {
+                    #assignment
+                    #tail
                 }
+            })
+        } else {
+            None
+        };
+
+        Some(quote! {
+            #clear
+            #maybe
+        })
+    }
+
+    /// Generate the `reset_` setter which restores the field to its default
+    /// value, if the `reset` argument is present.
+    fn generate_reset_setter(&self, context: &Context, common: &Common) -> Option<TokenStream2> {
+        if !self.attrs.reset {
+            return None;
+        }
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let reset = format_ident!("reset_{}", self.name);
+        let doc = format!("Reset `{}` to its default value", self.name);
+        let default = self
+            .default_expr()
+            .map_or_else(|| quote!(Default::default()), ToTokens::to_token_stream);
+        let assignment = self.store(context, &default);
+
+        Some(quote! {
+            #[doc = #doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #reset(mut self) -> #output #where_clause {
+                #assignment
+                #tail
             }
-            (false, true, Type::Bool(_)) => {
-                quote! {
-                    #doc
-                    #visibility fn #field(mut self) -> Self {
-                        self.#field = true;
-                        self
-                    }
+        })
+    }
+
+    /// Generate the setter which assigns a new value to the field.
+    fn generate_value_setter(&self, context: &Context, common: &Common) -> TokenStream2 {
+        let Common {
+            attrs,
+            forwarded,
+            visibility,
+            constness,
+            output,
+            tail,
+            where_clause,
+        } = common;
+        let field = &self.name;
+
+        let (arg_ty, wrappers) = if let Some(value_ty) = self.value_ty() {
+            value_ty
+        } else {
+            // a stripped bool
+            let assignment = self.store(context, &quote!(true));
+            return quote! {
+                #attrs
+                #visibility #constness fn #field(mut self) -> #output #where_clause {
+                    #assignment
+                    #tail
+                }
+            };
+        };
+
+        let assign = |value: TokenStream2| {
+            let value = wrappers
+                .iter()
+                .fold(value, |value, wrapper| quote!(#wrapper(#value)));
+            self.store(context, &value)
+        };
+
+        // the generics and argument type of an `into` setter
+        let (into_generics, into_arg_ty) = self.param_ty(context, &arg_ty);
+
+        if !self.attrs.into {
+            let assignment = assign(quote!(#field));
+            quote! {
+                #attrs
+                #visibility #constness fn #field(mut self, #field: #arg_ty) -> #output #where_clause {
+                    #assignment
+                    #tail
                 }
             }
-            (false, true, Type::Option(option)) => {
-                let inner_ty = option.inner_ty();
-                quote! {
-                    #doc
-                    #visibility fn #field(mut self, #field: #inner_ty) -> Self {
-                        self.#field = Some(#field);
-                        self
-                    }
+        } else if self.attrs.outline || self.attrs.wrap {
+            // a thin generic wrapper around a non-generic setter, so that only
+            // the conversion is instantiated for each input type, which is the
+            // default when the setter also allocates
+            let inner = format_ident!("__set_{}", field);
+            // inlining the inner function would undo the outlining
+            let inner_attrs = forwarded
+                .iter()
+                .filter(|attr| !attr.path().is_ident("inline"));
+            let assignment = assign(quote!(#field));
+            quote! {
+                #attrs
+                #visibility fn #field #into_generics(self, #field: #into_arg_ty) -> #output #where_clause {
+                    self.#inner(#field.into())
+                }
+
+                #(#[#inner_attrs])*
+                #[inline(never)]
+                fn #inner(mut self, #field: #arg_ty) -> #output #where_clause {
+                    #assignment
+                    #tail
                 }
             }
-            (false, false, _) => {
-                quote! {
-                    #doc
-                    #visibility fn #field(mut self, #field: #ty) -> Self {
-                        self.#field = #field;
-                        self
-                    }
+        } else {
+            let assignment = assign(quote!(#field.into()));
+            quote! {
+                #attrs
+                #visibility fn #field #into_generics(mut self, #field: #into_arg_ty) -> #output #where_clause {
+                    #assignment
+                    #tail
                 }
             }
         }
     }
+
+    /// The type accepted by the setter which assigns a new value to the field,
+    /// and the functions which wrap it before it's assigned, innermost first.
+    ///
+    /// Returns `None` for a stripped `bool`, whose setter takes no value.
+    fn value_ty(&self) -> Option<(TokenStream2, Vec<TokenStream2>)> {
+        if self.attrs.wrap {
+            return Some(self.wrapped_ty());
+        } else if let Some(dyn_ty) = self.dyn_ty() {
+            return Some(dyn_ty);
+        }
+
+        match (self.attrs.strip, &self.ty) {
+            (true, Type::Bool(_)) => {
+                assert!(
+                    !self.attrs.into,
+                    "can't use both 'strip' and 'into' on a bool"
+                );
+                None
+            }
+            (true, Type::Option(option)) => {
+                Some((option.inner_ty().to_token_stream(), vec![quote!(Some)]))
+            }
+            (true, _) => panic!("'strip' argument is only valid for `Option` and `bool` fields"),
+            (false, ty) => Some((ty.to_token_stream(), Vec::new())),
+        }
+    }
+
+    /// The generics and type of a parameter which accepts a value of the given
+    /// type, or anything which converts into it if the `into` argument is
+    /// present.
+    fn param_ty(
+        &self,
+        context: &Context,
+        ty: &impl ToTokens,
+    ) -> (Option<TokenStream2>, TokenStream2) {
+        if !self.attrs.into {
+            (None, ty.to_token_stream())
+        } else if self.attrs.generic {
+            let param = type_param(context.generics, "V");
+            (Some(quote!(<#param: Into<#ty>>)), quote!(#param))
+        } else {
+            (None, quote!(impl Into<#ty>))
+        }
+    }
+
+    /// The argument of a setter named after the field, converted with `into`
+    /// if the `into` argument is present.
+    fn converted_arg(&self) -> TokenStream2 {
+        let field = &self.name;
+        if self.attrs.into {
+            quote!(#field.into())
+        } else {
+            quote!(#field)
+        }
+    }
+
+    /// The expression which mutably borrows the value of the field, inserting
+    /// its default into a builder if it hasn't been set.
+    fn field_mut(&self, context: &Context) -> TokenStream2 {
+        let field = &self.name;
+        if context.builder {
+            let default = self
+                .default_expr()
+                .map_or_else(|| quote!(Default::default), |default| quote!(|| #default));
+            quote!(self.#field.get_or_insert_with(#default))
+        } else {
+            quote!(self.#field)
+        }
+    }
+
+    /// The statement which stores a value in the field.
+    fn store(&self, context: &Context, value: &TokenStream2) -> TokenStream2 {
+        let field = &self.name;
+        if context.builder {
+            quote!(self.#field = Some(#value);)
+        } else {
+            quote!(self.#field = #value;)
+        }
+    }
+}
+
+/// The parts shared by each of the setters generated for a field
+struct Common<'a> {
+    /// The attributes of the setter
+    attrs: TokenStream2,
+
+    /// The attributes forwarded from `#[set(attr(...))]` arguments
+    forwarded: Vec<&'a Meta>,
+
+    visibility: &'a Visibility,
+    constness: Option<Token![const]>,
+
+    /// The return type of the setter
+    output: TokenStream2,
+
+    /// The final expression of the setter, which returns the output
+    tail: TokenStream2,
+
+    where_clause: Option<WhereClause>,
+}
+
+impl<'a> Common<'a> {
+    fn new(field: &'a Field, context: &'a Context) -> Self {
+        let doc = field.attrs.doc_attribute();
+        let forwarded: Vec<_> = context.attrs.iter().chain(&field.attrs.attrs).collect();
+        let attrs = quote! {
+            #doc
+            #[must_use]
+            #(#[#forwarded])*
+        };
+        let constness = if field.attrs.constant {
+            Some(<Token![const]>::default())
+        } else {
+            None
+        };
+        let (output, tail) = match context.transition {
+            Some(transition) => (transition.ty.clone(), transition.expr.clone()),
+            None => (quote!(Self), quote!(self)),
+        };
+
+        Self {
+            attrs,
+            forwarded,
+            visibility: &field.attrs.visibility,
+            constness,
+            output,
+            tail,
+            where_clause: field.attrs.where_clause(),
+        }
+    }
+}
+
+/// Pick a name for the type parameter of a generic setter which doesn't
+/// shadow one of the struct's own type parameters.
+fn type_param(generics: &Generics, base: &str) -> Ident {
+    let taken: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    let mut ident = format_ident!("{}", base);
+    let mut i = 0_usize;
+    while taken.contains(&&ident) {
+        ident = format_ident!("{}{}", base, i);
+        i += 1;
+    }
+    ident
 }
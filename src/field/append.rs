@@ -0,0 +1,57 @@
+use super::{type_param, Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+impl Field {
+    /// Generate the setter which appends to a string or path, if the `append`
+    /// argument is present.
+    pub(super) fn generate_append_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        let method = self.attrs.append.as_ref()?;
+        let field = &self.name;
+        assert!(
+            method != field,
+            "the 'append' setter of `{}` must have a different name to the field",
+            field
+        );
+
+        let kind = match &self.ty {
+            Type::Buffer(buffer) => buffer.kind(),
+            _ => panic!(
+                "'append' argument is only valid for `String`, `OsString` and `PathBuf` fields"
+            ),
+        };
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let doc = format!("Append to `{}`", field);
+        let target = self.field_mut(context);
+        let append = kind.append_method();
+        let borrowed_ty = kind.borrowed_ty();
+        let (generics, arg_ty) = if self.attrs.generic {
+            let param = type_param(context.generics, "V");
+            (Some(quote!(<#param: AsRef<#borrowed_ty>>)), quote!(#param))
+        } else {
+            (None, quote!(impl AsRef<#borrowed_ty>))
+        };
+
+        Some(quote! {
+            #[doc = #doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #method #generics(mut self, value: #arg_ty) -> #output #where_clause {
+                #target.#append(value.as_ref());
+                #tail
+            }
+        })
+    }
+}
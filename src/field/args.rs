@@ -1,13 +1,49 @@
 use crate::field::visibility::Visibility;
+use proc_macro2::Span;
 use std::convert::{TryFrom, TryInto};
-use syn::{punctuated::Punctuated, token::Comma, Attribute, Meta, NestedMeta};
+use syn::{
+    parse::Parse, punctuated::Punctuated, token::Comma, Attribute, Expr, Ident, Lit, LitStr, Meta,
+    NestedMeta, WherePredicate,
+};
 
-/// The arguments within the `#[set(...)]` field attribute
+/// The arguments within the `#[set(...)]` field or struct attribute
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Default, PartialEq)]
 pub struct Args {
     pub into: bool,
     pub strip: bool,
+    pub constant: bool,
+    pub outline: bool,
+    pub generic: bool,
+    pub retype: bool,
+    pub required: bool,
+    pub reset: bool,
+    pub clear: bool,
+    pub maybe: bool,
+    pub extend: bool,
+    pub wrap: bool,
+    pub interior: bool,
+    pub swap: bool,
+    pub index: bool,
+    pub fill: bool,
+    pub at_const: bool,
+    pub flag: bool,
+    pub conditional: bool,
+    pub try_into: bool,
+    pub raw: bool,
+    pub destructure: bool,
+    pub typestate: bool,
+    pub impl_default: bool,
     pub visibility: Visibility,
+    pub attrs: Vec<Meta>,
+    pub bounds: Vec<WherePredicate>,
+    pub default: Option<Expr>,
+    pub each: Option<Ident>,
+    pub append: Option<Ident>,
+    pub ordering: Option<Ident>,
+    pub units: Vec<Ident>,
+    pub arg_names: Vec<Ident>,
+    pub negate: Option<Ident>,
 }
 
 impl<'a> TryFrom<&'a Attribute> for Args {
@@ -39,6 +75,12 @@ pub enum FromAttributeError {
     #[error("unrecognised attribute")]
     UnrecognisedAttribute,
 
+    #[error("argument is only valid on fields")]
+    FieldOnlyArg,
+
+    #[error("argument is only valid on structs")]
+    StructOnlyArg,
+
     #[error(transparent)]
     FromPunctuated(#[from] FromPunctuatedError),
 }
@@ -56,10 +98,72 @@ impl<'a> TryFrom<&'a Punctuated<NestedMeta, Comma>> for Args {
                 return Err(FromPunctuatedError::UnrecognisedArg);
             };
 
-            if parse_nested_ident(meta, "into") {
-                try_set_bool(&mut args.into)?;
-            } else if parse_nested_ident(meta, "strip") {
-                try_set_bool(&mut args.strip)?;
+            if let Some(flag) = args.flag_mut(meta) {
+                try_set_bool(flag)?;
+            } else if parse_nested_ident(meta, "units") {
+                let units = DEFAULT_UNITS
+                    .iter()
+                    .map(|unit| Ident::new(unit, Span::call_site()))
+                    .collect();
+                try_set_units(&mut args.units, units)?;
+            } else if let Some(bounds) = parse_nested_str(meta, "bound") {
+                let bounds = bounds
+                    .parse_with(Punctuated::<WherePredicate, Comma>::parse_terminated)
+                    .map_err(|_| FromPunctuatedError::InvalidBound)?;
+                args.bounds.extend(bounds);
+            } else if let Some(default) = parse_nested_str(meta, "default") {
+                try_set_parsed(
+                    &mut args.default,
+                    default,
+                    FromPunctuatedError::InvalidDefault,
+                )?;
+            } else if let Some(each) = parse_nested_str(meta, "each") {
+                try_set_parsed(&mut args.each, each, FromPunctuatedError::InvalidEach)?;
+            } else if let Some(append) = parse_nested_str(meta, "append") {
+                try_set_parsed(&mut args.append, append, FromPunctuatedError::InvalidAppend)?;
+            } else if let Some(negate) = parse_nested_str(meta, "negate") {
+                try_set_parsed(&mut args.negate, negate, FromPunctuatedError::InvalidNegate)?;
+            } else if let Some(ordering) = parse_nested_str(meta, "ordering") {
+                try_set_parsed(
+                    &mut args.ordering,
+                    ordering,
+                    FromPunctuatedError::InvalidOrdering,
+                )?;
+                if !ORDERINGS
+                    .iter()
+                    .any(|name| args.ordering.as_ref().unwrap() == name)
+                {
+                    return Err(FromPunctuatedError::InvalidOrdering);
+                }
+            } else if let Some(nested) = parse_nested_list(meta, "units") {
+                try_set_units(&mut args.units, parse_units(nested)?)?;
+            } else if let Some(nested) = parse_nested_list(meta, "index") {
+                try_set_bool(&mut args.index)?;
+                for nested_meta in nested {
+                    let flag = match nested_meta {
+                        NestedMeta::Meta(meta) if parse_nested_ident(meta, "fill") => {
+                            &mut args.fill
+                        }
+                        NestedMeta::Meta(meta) if parse_nested_ident(meta, "at_const") => {
+                            &mut args.at_const
+                        }
+                        _ => return Err(FromPunctuatedError::InvalidIndexSetter),
+                    };
+                    try_set_bool(flag)?;
+                }
+            } else if let Some(nested) = parse_nested_list(meta, "args") {
+                if !args.arg_names.is_empty() {
+                    return Err(FromPunctuatedError::DuplicateArgs);
+                }
+                args.arg_names = parse_arg_names(nested)?;
+            } else if let Some(nested) = parse_nested_list(meta, "attr") {
+                for nested_meta in nested {
+                    if let NestedMeta::Meta(meta) = nested_meta {
+                        args.attrs.push(meta.clone());
+                    } else {
+                        return Err(FromPunctuatedError::UnrecognisedArg);
+                    }
+                }
             } else if let Some(Ok(visibility)) = Visibility::from_meta(meta) {
                 args.visibility = visibility;
             } else {
@@ -71,6 +175,78 @@ impl<'a> TryFrom<&'a Punctuated<NestedMeta, Comma>> for Args {
     }
 }
 
+impl Args {
+    /// Parse the `#[set(...)]` attribute of a struct, if it has one, failing
+    /// on arguments which only apply to fields.
+    pub fn from_struct_attrs(attrs: &[Attribute]) -> Result<Option<Self>, FromAttributeError> {
+        let mut set = None;
+
+        for attr in attrs {
+            match Self::try_from(attr) {
+                Ok(args) => set = Some(args),
+                Err(FromAttributeError::UnrecognisedAttribute) => (),
+                Err(error) => return Err(error),
+            }
+        }
+
+        match set {
+            Some(args) if !args.is_struct_level() => Err(FromAttributeError::FieldOnlyArg),
+            set => Ok(set),
+        }
+    }
+
+    /// Whether every argument present applies to a whole struct.
+    fn is_struct_level(&self) -> bool {
+        let struct_args = Self {
+            typestate: self.typestate,
+            impl_default: self.impl_default,
+            attrs: self.attrs.clone(),
+            bounds: self.bounds.clone(),
+            ..Self::default()
+        };
+        *self == struct_args
+    }
+
+    /// Whether any argument present only applies to a whole struct.
+    pub fn has_struct_only_args(&self) -> bool {
+        self.typestate || self.impl_default
+    }
+
+    /// The switch set by a bare argument, such as `into`, if the argument is
+    /// one.
+    fn flag_mut(&mut self, meta: &Meta) -> Option<&mut bool> {
+        let ident = match meta {
+            Meta::Path(path) => path.get_ident()?.to_string(),
+            _ => return None,
+        };
+        Some(match ident.as_str() {
+            "into" => &mut self.into,
+            "strip" => &mut self.strip,
+            "const" => &mut self.constant,
+            "outline" => &mut self.outline,
+            "generic" => &mut self.generic,
+            "retype" => &mut self.retype,
+            "required" => &mut self.required,
+            "reset" => &mut self.reset,
+            "clear" => &mut self.clear,
+            "maybe" => &mut self.maybe,
+            "extend" => &mut self.extend,
+            "wrap" => &mut self.wrap,
+            "interior" => &mut self.interior,
+            "swap" => &mut self.swap,
+            "index" => &mut self.index,
+            "flag" => &mut self.flag,
+            "conditional" => &mut self.conditional,
+            "try_into" => &mut self.try_into,
+            "raw" => &mut self.raw,
+            "destructure" => &mut self.destructure,
+            "typestate" => &mut self.typestate,
+            "impl_default" => &mut self.impl_default,
+            _ => return None,
+        })
+    }
+}
+
 #[derive(Debug, thiserror::Error, PartialEq)]
 pub enum FromPunctuatedError {
     #[error("duplicate arguments")]
@@ -78,8 +254,45 @@ pub enum FromPunctuatedError {
 
     #[error("unrecognised argument")]
     UnrecognisedArg,
+
+    #[error("invalid where-predicate in 'bound' argument")]
+    InvalidBound,
+
+    #[error("invalid expression in 'default' argument")]
+    InvalidDefault,
+
+    #[error("invalid method name in 'each' argument")]
+    InvalidEach,
+
+    #[error("invalid method name in 'append' argument")]
+    InvalidAppend,
+
+    #[error("invalid atomic memory ordering in 'ordering' argument")]
+    InvalidOrdering,
+
+    #[error("invalid unit in 'units' argument")]
+    InvalidUnit,
+
+    #[error("invalid parameter name in 'args' argument")]
+    InvalidArgName,
+
+    #[error("invalid method name in 'negate' argument")]
+    InvalidNegate,
+
+    #[error("invalid setter in 'index' argument")]
+    InvalidIndexSetter,
 }
 
+/// The variants of `std::sync::atomic::Ordering`
+const ORDERINGS: &[&str] = &["Relaxed", "Release", "Acquire", "AcqRel", "SeqCst"];
+
+/// The units which `Duration` setters can be generated for, named after the
+/// `Duration::from_*` constructors
+const UNITS: &[&str] = &["secs", "millis", "micros", "nanos", "secs_f64", "secs_f32"];
+
+/// The units generated by a bare `units` argument
+const DEFAULT_UNITS: &[&str] = &["secs", "millis", "micros", "secs_f64"];
+
 fn try_set_bool(flag: &mut bool) -> Result<(), FromPunctuatedError> {
     if *flag {
         Err(FromPunctuatedError::DuplicateArgs)
@@ -98,14 +311,90 @@ fn parse_nested_ident(meta: &Meta, ident: &str) -> bool {
     }
 }
 
+/// Set an optional argument by parsing a string literal, failing if it has
+/// already been set.
+fn try_set_parsed<T: Parse>(
+    arg: &mut Option<T>,
+    lit: &LitStr,
+    error: FromPunctuatedError,
+) -> Result<(), FromPunctuatedError> {
+    if arg.is_some() {
+        return Err(FromPunctuatedError::DuplicateArgs);
+    }
+    *arg = Some(lit.parse().map_err(|_| error)?);
+    Ok(())
+}
+
+fn try_set_units(units: &mut Vec<Ident>, new: Vec<Ident>) -> Result<(), FromPunctuatedError> {
+    if units.is_empty() {
+        *units = new;
+        Ok(())
+    } else {
+        Err(FromPunctuatedError::DuplicateArgs)
+    }
+}
+
+/// parse the units listed in a `units(...)` argument
+fn parse_units(nested: &Punctuated<NestedMeta, Comma>) -> Result<Vec<Ident>, FromPunctuatedError> {
+    nested
+        .iter()
+        .map(|nested_meta| match nested_meta {
+            NestedMeta::Meta(Meta::Path(path)) => path
+                .get_ident()
+                .filter(|unit| UNITS.iter().any(|name| *unit == name))
+                .cloned()
+                .ok_or(FromPunctuatedError::InvalidUnit),
+            _ => Err(FromPunctuatedError::InvalidUnit),
+        })
+        .collect()
+}
+
+/// parse the parameter names listed in an `args(...)` argument
+fn parse_arg_names(
+    nested: &Punctuated<NestedMeta, Comma>,
+) -> Result<Vec<Ident>, FromPunctuatedError> {
+    nested
+        .iter()
+        .map(|nested_meta| match nested_meta {
+            NestedMeta::Meta(Meta::Path(path)) => path
+                .get_ident()
+                .cloned()
+                .ok_or(FromPunctuatedError::InvalidArgName),
+            _ => Err(FromPunctuatedError::InvalidArgName),
+        })
+        .collect()
+}
+
+/// parse a name-value attribute with a string literal value
+fn parse_nested_str<'a>(meta: &'a Meta, ident: &str) -> Option<&'a LitStr> {
+    if let Meta::NameValue(name_value) = meta {
+        if name_value.path.is_ident(ident) {
+            if let Lit::Str(lit_str) = &name_value.lit {
+                return Some(lit_str);
+            }
+        }
+    }
+    None
+}
+
+/// parse a list-like attribute, returning its nested arguments
+fn parse_nested_list<'a>(meta: &'a Meta, ident: &str) -> Option<&'a Punctuated<NestedMeta, Comma>> {
+    if let Meta::List(list) = meta {
+        if list.path.is_ident(ident) {
+            return Some(&list.nested);
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Args, FromAttributeError, FromPunctuatedError};
     use crate::field::visibility::Visibility;
     use std::convert::{TryFrom, TryInto};
     use syn::{
-        parse::Parser, parse_quote::ParseQuote, punctuated::Punctuated, token::Comma, Attribute,
-        Meta, NestedMeta,
+        parse::Parser, parse_quote, parse_quote::ParseQuote, punctuated::Punctuated, token::Comma,
+        Attribute, Meta, NestedMeta,
     };
     use test_case::test_case;
 
@@ -118,13 +407,55 @@ mod tests {
         }
     }
 
-    #[test_case("into" => Ok(Args {into: true, strip: false, visibility: super::Visibility::private()}) ; "into")]
-    #[test_case("strip" => Ok(Args {into: false, strip: true, visibility: super::Visibility::private()}) ; "strip")]
-    #[test_case("into, strip" => Ok(Args {into: true, strip: true, visibility: super::Visibility::private()}) ; "into, strip")]
+    #[test_case("into" => Ok(Args {into: true, ..Args::default()}) ; "into")]
+    #[test_case("strip" => Ok(Args {strip: true, ..Args::default()}) ; "strip")]
+    #[test_case("into, strip" => Ok(Args {into: true, strip: true, ..Args::default()}) ; "into, strip")]
     #[test_case("unrecognised" => Err(FromPunctuatedError::UnrecognisedArg) ; "unrecognised argument")]
     #[test_case("into, into" => Err(FromPunctuatedError::DuplicateArgs) ; "duplicate arguments")]
-    #[test_case("pub" => Ok(Args {into: false, strip: false, visibility: super::Visibility::public()}) ; "public")]
-    #[test_case(r#"pub = "crate""# => Ok(Args {into: false, strip: false, visibility: super::Visibility::in_crate()}) ; "pub in crate")]
+    #[test_case("const" => Ok(Args {constant: true, ..Args::default()}) ; "const arg")]
+    #[test_case("into, outline" => Ok(Args {into: true, outline: true, ..Args::default()}) ; "outline")]
+    #[test_case("into, generic" => Ok(Args {into: true, generic: true, ..Args::default()}) ; "generic")]
+    #[test_case("retype" => Ok(Args {retype: true, ..Args::default()}) ; "retype")]
+    #[test_case("required" => Ok(Args {required: true, ..Args::default()}) ; "required")]
+    #[test_case("reset" => Ok(Args {reset: true, ..Args::default()}) ; "reset")]
+    #[test_case("strip, clear, maybe" => Ok(Args {strip: true, clear: true, maybe: true, ..Args::default()}) ; "clear and maybe")]
+    #[test_case("extend" => Ok(Args {extend: true, ..Args::default()}) ; "extend")]
+    #[test_case("wrap" => Ok(Args {wrap: true, ..Args::default()}) ; "wrap")]
+    #[test_case("interior" => Ok(Args {interior: true, ..Args::default()}) ; "interior")]
+    #[test_case("index" => Ok(Args {index: true, ..Args::default()}) ; "index")]
+    #[test_case("index(fill, at_const)" => Ok(Args {index: true, fill: true, at_const: true, ..Args::default()}) ; "index setters")]
+    #[test_case("index, index(fill)" => Err(FromPunctuatedError::DuplicateArgs) ; "duplicate index")]
+    #[test_case("index(fill, fill)" => Err(FromPunctuatedError::DuplicateArgs) ; "duplicate index setter")]
+    #[test_case("index(swap)" => Err(FromPunctuatedError::InvalidIndexSetter) ; "invalid index setter")]
+    #[test_case("typestate" => Ok(Args {typestate: true, ..Args::default()}) ; "typestate")]
+    #[test_case("impl_default" => Ok(Args {impl_default: true, ..Args::default()}) ; "impl default")]
+    #[test_case(r#"bound = "T: Clone, U: Default""# => Ok(Args {bounds: vec![parse_quote!(T: Clone), parse_quote!(U: Default)], ..Args::default()}) ; "bound")]
+    #[test_case(r#"bound = "T:""# => Ok(Args {bounds: vec![parse_quote!(T:)], ..Args::default()}) ; "empty bound")]
+    #[test_case(r#"bound = "T Clone""# => Err(FromPunctuatedError::InvalidBound) ; "invalid bound")]
+    #[test_case(r#"default = "Duration::from_secs(5)""# => Ok(Args {default: Some(parse_quote!(Duration::from_secs(5))), ..Args::default()}) ; "default")]
+    #[test_case(r#"default = "1", default = "2""# => Err(FromPunctuatedError::DuplicateArgs) ; "duplicate default")]
+    #[test_case(r#"default = "1 +""# => Err(FromPunctuatedError::InvalidDefault) ; "invalid default")]
+    #[test_case(r#"each = "arg""# => Ok(Args {each: Some(parse_quote!(arg)), ..Args::default()}) ; "each")]
+    #[test_case(r#"each = "two words""# => Err(FromPunctuatedError::InvalidEach) ; "invalid each")]
+    #[test_case(r#"append = "push_arg""# => Ok(Args {append: Some(parse_quote!(push_arg)), ..Args::default()}) ; "append")]
+    #[test_case(r#"append = "1""# => Err(FromPunctuatedError::InvalidAppend) ; "invalid append")]
+    #[test_case(r#"ordering = "Release", swap"# => Ok(Args {ordering: Some(parse_quote!(Release)), swap: true, ..Args::default()}) ; "ordering")]
+    #[test_case(r#"ordering = "Strict""# => Err(FromPunctuatedError::InvalidOrdering) ; "invalid ordering")]
+    #[test_case("units" => Ok(Args {units: vec![parse_quote!(secs), parse_quote!(millis), parse_quote!(micros), parse_quote!(secs_f64)], ..Args::default()}) ; "units")]
+    #[test_case("units(secs, millis)" => Ok(Args {units: vec![parse_quote!(secs), parse_quote!(millis)], ..Args::default()}) ; "unit list")]
+    #[test_case("units(hours)" => Err(FromPunctuatedError::InvalidUnit) ; "invalid unit")]
+    #[test_case("args(width, height)" => Ok(Args {arg_names: vec![parse_quote!(width), parse_quote!(height)], ..Args::default()}) ; "args")]
+    #[test_case("args(\"width\")" => Err(FromPunctuatedError::InvalidArgName) ; "literal args")]
+    #[test_case(r#"flag, negate = "no_colour""# => Ok(Args {flag: true, negate: Some(parse_quote!(no_colour)), ..Args::default()}) ; "flag")]
+    #[test_case(r#"negate = "no colour""# => Err(FromPunctuatedError::InvalidNegate) ; "invalid negate")]
+    #[test_case("conditional" => Ok(Args {conditional: true, ..Args::default()}) ; "conditional")]
+    #[test_case("try_into" => Ok(Args {try_into: true, ..Args::default()}) ; "try into")]
+    #[test_case("raw" => Ok(Args {raw: true, ..Args::default()}) ; "raw")]
+    #[test_case("destructure" => Ok(Args {destructure: true, ..Args::default()}) ; "destructure")]
+    #[test_case("attr(inline, cfg(test))" => Ok(Args {attrs: vec![parse_quote!(inline), parse_quote!(cfg(test))], ..Args::default()}) ; "forwarded attributes")]
+    #[test_case("attr(\"inline\")" => Err(FromPunctuatedError::UnrecognisedArg) ; "literal forwarded attribute")]
+    #[test_case("pub" => Ok(Args {visibility: super::Visibility::public(), ..Args::default()}) ; "public")]
+    #[test_case(r#"pub = "crate""# => Ok(Args {visibility: super::Visibility::in_crate(), ..Args::default()}) ; "pub in crate")]
     fn parse_from_field_args(input: &str) -> Result<Args, FromPunctuatedError> {
         let parser = Punctuated::<NestedMeta, Comma>::parse_separated_nonempty;
         let args = parser.parse_str(input).unwrap();
@@ -132,13 +463,24 @@ mod tests {
         Args::try_from(&args)
     }
 
-    #[test_case("#[set(into)]" => Ok(Args {into: true, strip: false, visibility: super::Visibility::private()}) ; "into")]
-    #[test_case(r#"#[set(into, strip, pub = "crate")]"# => Ok(Args {into: true, strip: true, visibility: super::Visibility::in_crate()}) ; "everything")]
+    #[test_case("#[set(into)]" => Ok(Args {into: true, ..Args::default()}) ; "into")]
+    #[test_case(r#"#[set(into, strip, pub = "crate")]"# => Ok(Args {into: true, strip: true, visibility: super::Visibility::in_crate(), ..Args::default()}) ; "everything")]
     #[test_case("#[det(into)]" => Err(FromAttributeError::UnrecognisedAttribute))]
-    #[test_case("#[set]" => Ok(Args {into: false, strip: false, visibility: super::Visibility::private()}) ; "no args")]
+    #[test_case("#[set]" => Ok(Args::default()) ; "no args")]
     fn parse_from_field_attribute(input: &str) -> Result<Args, FromAttributeError> {
         let parser = Attribute::parse_outer;
         let attribute = &parser.parse_str(input).unwrap()[0];
         attribute.try_into()
     }
+
+    #[test_case("#[set(typestate, attr(inline))]" => Ok(Some(Args {typestate: true, attrs: vec![parse_quote!(inline)], ..Args::default()})) ; "struct args")]
+    #[test_case("#[derive(Debug)]" => Ok(None) ; "no set attribute")]
+    #[test_case("#[set(into)]" => Err(FromAttributeError::FieldOnlyArg) ; "field arg")]
+    #[test_case("#[set(impl_defualt)]" => Err(FromAttributeError::FromPunctuated(FromPunctuatedError::UnrecognisedArg)) ; "unrecognised arg")]
+    #[test_case("#[set(attr = 1)]" => Err(FromAttributeError::FromPunctuated(FromPunctuatedError::UnrecognisedArg)) ; "malformed arg")]
+    fn parse_from_struct_attributes(input: &str) -> Result<Option<Args>, FromAttributeError> {
+        let parser = Attribute::parse_outer;
+        let attrs = parser.parse_str(input).unwrap();
+        Args::from_struct_attrs(&attrs)
+    }
 }
@@ -0,0 +1,161 @@
+use super::{ty::ArrayTy, Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Expr};
+
+impl Field {
+    /// Generate the `_at` setter of an array field if the `index` argument is
+    /// present, along with the `_fill` and `_at_const` setters if they're
+    /// listed in it, as in `index(fill, at_const)`.
+    ///
+    /// `_fill` is opt-in since it needs the element type to be `Clone`, and
+    /// `_at_const` since it needs Rust 1.57.
+    pub(super) fn generate_index_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if !self.attrs.index {
+            return None;
+        }
+
+        let array = match &self.ty {
+            Type::Array(array) => array,
+            _ => panic!("'index' argument is only valid for array fields"),
+        };
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let element_ty = array.element_ty();
+        let target = self.field_mut(context);
+        let (generics, arg_ty) = self.param_ty(context, element_ty);
+        let (value, convert) = if self.attrs.into {
+            (
+                quote!(value.into()),
+                Some(quote!(let value: #element_ty = value.into();)),
+            )
+        } else {
+            (quote!(value), None)
+        };
+
+        let at = format_ident!("{}_at", field);
+        let at_doc = format!(
+            "Set the element of `{}` at an index\n\n# Panics\n\nPanics if the index is out of \
+             bounds",
+            field
+        );
+        let fill = if self.attrs.fill {
+            let fill = format_ident!("{}_fill", field);
+            let doc = format!("Set every element of `{}` to a value", field);
+            let mut where_clause = where_clause.clone().unwrap_or_else(|| parse_quote!(where));
+            where_clause
+                .predicates
+                .push(parse_quote!(#element_ty: Clone));
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility fn #fill #generics(mut self, value: #arg_ty) -> #output #where_clause {
+                    #convert
+                    for element in #target.iter_mut() {
+                        *element = value.clone();
+                    }
+                    #tail
+                }
+            })
+        } else {
+            None
+        };
+        let at_const = if self.attrs.at_const {
+            Some(self.generate_at_const_setter(context, common, array))
+        } else {
+            None
+        };
+
+        Some(quote! {
+            #[doc = #at_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #at #generics(mut self, index: usize, value: #arg_ty) -> #output #where_clause {
+                #target[index] = #value;
+                #tail
+            }
+
+            #fill
+            #at_const
+        })
+    }
+
+    /// Generate the `_at_const` setter, which takes the index as a const
+    /// generic parameter and fails to compile if it's out of bounds.
+    ///
+    /// The value isn't converted with `into`, since `impl Trait` parameters
+    /// would prevent the index from being given explicitly.
+    fn generate_at_const_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+        array: &ArrayTy,
+    ) -> TokenStream2 {
+        let len = const_arg(array.len()).unwrap_or_else(|| {
+            panic!(
+                "'at_const' setter of `{}` needs the array length to be a literal or a single \
+                 identifier",
+                self.name
+            )
+        });
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let target = self.field_mut(context);
+        let element_ty = array.element_ty();
+
+        let at_const = format_ident!("{}_at_const", field);
+        let doc = format!(
+            "Set the element of `{}` at an index which is checked at compile time\n\nRequires Rust \
+             1.57 or later",
+            field
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #at_const<const INDEX: usize>(mut self, value: #element_ty) -> #output #where_clause {
+                struct InBounds<const INDEX: usize, const LEN: usize>;
+                impl<const INDEX: usize, const LEN: usize> InBounds<INDEX, LEN> {
+                    const CHECK: () = assert!(INDEX < LEN, "index out of bounds");
+                }
+                let () = InBounds::<INDEX, #len>::CHECK;
+
+                #target[INDEX] = value;
+                #tail
+            }
+        }
+    }
+}
+
+/// The length of an array if it can be given as a const generic argument
+/// as it is, which is the case for a literal, or for a const generic
+/// parameter or constant named by a single identifier.
+fn const_arg(len: &Expr) -> Option<&Expr> {
+    match len {
+        Expr::Lit(_) => Some(len),
+        Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => Some(len),
+        _ => None,
+    }
+}
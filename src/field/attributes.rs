@@ -1,16 +1,49 @@
 use crate::field::args::{Args, FromAttributeError};
 use quote::quote;
 use std::convert::TryFrom;
-use syn::{parse::Parser, Attribute, Lit, Meta, MetaNameValue};
+use syn::{
+    parse::Parser, parse_quote, Attribute, Expr, Ident, Lit, Meta, MetaNameValue, WhereClause,
+    WherePredicate,
+};
 
 use super::visibility::Visibility;
 
 /// The full set of possible field attributes that this crate is interested in
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Default, Debug, PartialEq)]
 pub struct Attributes {
     pub into: bool,
     pub strip: bool,
+    pub constant: bool,
+    pub outline: bool,
+    pub generic: bool,
+    pub retype: bool,
+    pub required: bool,
+    pub reset: bool,
+    pub clear: bool,
+    pub maybe: bool,
+    pub extend: bool,
+    pub wrap: bool,
+    pub interior: bool,
+    pub swap: bool,
+    pub index: bool,
+    pub fill: bool,
+    pub at_const: bool,
+    pub flag: bool,
+    pub conditional: bool,
+    pub try_into: bool,
+    pub raw: bool,
+    pub destructure: bool,
     pub visibility: Visibility,
+    pub attrs: Vec<Meta>,
+    pub bounds: Vec<WherePredicate>,
+    pub default: Option<Expr>,
+    pub each: Option<Ident>,
+    pub append: Option<Ident>,
+    pub ordering: Option<Ident>,
+    pub units: Vec<Ident>,
+    pub arg_names: Vec<Ident>,
+    pub negate: Option<Ident>,
     pub doc: Option<String>,
 }
 
@@ -20,21 +53,55 @@ impl Attributes {
         let mut doc = None;
 
         for attr in attrs.iter() {
-            if let Ok(a) = Args::try_from(attr) {
-                set = Some(a);
-            } else if let Ok(Meta::NameValue(name_value)) = attr.parse_meta() {
-                match parse_doc(&name_value) {
-                    Some(Ok(s)) => doc = Some(s),
-                    Some(Err(())) => return Err(FromAttributeError::MalformedAttribute),
-                    None => (),
+            match Args::try_from(attr) {
+                Ok(a) if a.has_struct_only_args() => return Err(FromAttributeError::StructOnlyArg),
+                Ok(a) => set = Some(a),
+                Err(FromAttributeError::UnrecognisedAttribute) => {
+                    if let Ok(Meta::NameValue(name_value)) = attr.parse_meta() {
+                        match parse_doc(&name_value) {
+                            Some(Ok(s)) => doc = Some(s),
+                            Some(Err(())) => return Err(FromAttributeError::MalformedAttribute),
+                            None => (),
+                        }
+                    }
                 }
+                Err(error) => return Err(error),
             }
         }
 
         Ok(set.map(|set| Self {
             into: set.into,
             strip: set.strip,
+            constant: set.constant,
+            outline: set.outline,
+            generic: set.generic,
+            retype: set.retype,
+            required: set.required,
+            reset: set.reset,
+            clear: set.clear,
+            maybe: set.maybe,
+            extend: set.extend,
+            wrap: set.wrap,
+            interior: set.interior,
+            swap: set.swap,
+            index: set.index,
+            fill: set.fill,
+            at_const: set.at_const,
+            flag: set.flag,
+            conditional: set.conditional,
+            try_into: set.try_into,
+            raw: set.raw,
+            destructure: set.destructure,
             visibility: set.visibility,
+            attrs: set.attrs,
+            bounds: set.bounds,
+            default: set.default,
+            each: set.each,
+            append: set.append,
+            ordering: set.ordering,
+            units: set.units,
+            arg_names: set.arg_names,
+            negate: set.negate,
             doc,
         }))
     }
@@ -47,6 +114,14 @@ impl Attributes {
         };
         Some(parser.parse2(tokens).unwrap().into_iter().next().unwrap())
     }
+
+    pub fn where_clause(&self) -> Option<WhereClause> {
+        if self.bounds.is_empty() {
+            return None;
+        }
+        let bounds = &self.bounds;
+        Some(parse_quote!(where #(#bounds),*))
+    }
 }
 
 fn parse_doc(name_value: &MetaNameValue) -> Option<Result<String, ()>> {
@@ -62,10 +137,10 @@ fn parse_doc(name_value: &MetaNameValue) -> Option<Result<String, ()>> {
 #[cfg(test)]
 mod tests {
     use super::Attributes;
-    use crate::field::visibility::Visibility;
+    use crate::field::args::{FromAttributeError, FromPunctuatedError};
     use proc_macro2::TokenStream as TokenStream2;
     use quote::quote;
-    use syn::{parse::Parser, Field};
+    use syn::{parse::Parser, parse_quote, Field};
     use test_case::test_case;
 
     #[test_case(
@@ -73,7 +148,7 @@ mod tests {
             #[set(into, strip)]
             some_field: String
         }
-        => Attributes {into: true, strip: true, visibility: super::Visibility::private(), doc: None}
+        => Attributes {into: true, strip: true, ..Attributes::default()}
         ; "plain attributes"
     )]
     #[test_case(
@@ -82,7 +157,7 @@ mod tests {
             #[set(into, strip)]
             some_field: String
         }
-        => Attributes {into: true, strip: true, visibility: super::Visibility::private(), doc: Some(" This is a doc comment".to_string())}
+        => Attributes {into: true, strip: true, doc: Some(" This is a doc comment".to_string()), ..Attributes::default()}
         ; "with doc comment"
     )]
     #[test_case(
@@ -92,7 +167,7 @@ mod tests {
             #[unrelated]
             some_field: String
         }
-        => Attributes {into: true, strip: true, visibility: super::Visibility::private(), doc: Some(" This is a doc comment".to_string())}
+        => Attributes {into: true, strip: true, doc: Some(" This is a doc comment".to_string()), ..Attributes::default()}
         ; "unrelated attributes"
     )]
     #[test_case(
@@ -100,13 +175,46 @@ mod tests {
             #[set]
             some_field: String
         }
-        => Attributes {into: false, strip: false, visibility: super::Visibility::private(), doc: None}
+        => Attributes::default()
         ; "empty attributes"
     )]
+    #[test_case(
+        quote!{
+            #[set(attr(inline))]
+            some_field: String
+        }
+        => Attributes {attrs: vec![parse_quote!(inline)], ..Attributes::default()}
+        ; "forwarded attributes"
+    )]
     fn from_attributes(tokens: TokenStream2) -> Attributes {
         let parser = Field::parse_named;
         let raw_attrs = parser.parse2(tokens).unwrap().attrs;
 
         Attributes::try_from_attrs(&raw_attrs).unwrap().unwrap()
     }
+
+    #[test_case(
+        quote!{
+            #[set(typestate)]
+            some_field: String
+        }
+        => Err(FromAttributeError::StructOnlyArg)
+        ; "struct argument"
+    )]
+    #[test_case(
+        quote!{
+            #[set(intoo)]
+            some_field: String
+        }
+        => Err(FromAttributeError::FromPunctuated(FromPunctuatedError::UnrecognisedArg))
+        ; "unrecognised argument"
+    )]
+    fn from_invalid_attributes(
+        tokens: TokenStream2,
+    ) -> Result<Option<Attributes>, FromAttributeError> {
+        let parser = Field::parse_named;
+        let raw_attrs = parser.parse2(tokens).unwrap().attrs;
+
+        Attributes::try_from_attrs(&raw_attrs)
+    }
 }
@@ -0,0 +1,215 @@
+use super::{ty::CollectionTy, type_param, Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+impl Field {
+    /// Generate the setter which adds a single element to a collection, or a
+    /// single entry to a map, if the `each` argument is present.
+    pub(super) fn generate_each_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        let method = self.attrs.each.as_ref()?;
+        let field = &self.name;
+        assert!(
+            method != field,
+            "the 'each' setter of `{}` must have a different name to the field",
+            field
+        );
+
+        let (collection, target) = self.collection(context, "each");
+        let add = collection.kind().add_method();
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+
+        if collection.kind().is_map() {
+            let (generics, key_ty, value_ty) = self.entry_params(context, &collection);
+            let generics = if generics.is_empty() {
+                None
+            } else {
+                Some(quote!(<#(#generics),*>))
+            };
+            let doc = format!("Insert an entry into `{}`", field);
+            return Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility fn #method #generics(mut self, key: #key_ty, value: #value_ty) -> #output #where_clause {
+                    #target.#add(key.into(), value.into());
+                    #tail
+                }
+            });
+        }
+
+        let doc = format!("Add an element to `{}`", field);
+        let (generics, arg_ty) = self.param_ty(context, collection.element_ty());
+        let item = if self.attrs.into {
+            quote!(item.into())
+        } else {
+            quote!(item)
+        };
+
+        Some(quote! {
+            #[doc = #doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #method #generics(mut self, item: #arg_ty) -> #output #where_clause {
+                #target.#add(#item);
+                #tail
+            }
+        })
+    }
+
+    /// Generate the `extend_` and `replace_` setters which add every item
+    /// from an iterator to a collection, if the `extend` argument is present.
+    pub(super) fn generate_extend_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if !self.attrs.extend {
+            return None;
+        }
+
+        let (collection, target) = self.collection(context, "extend");
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let extend = format_ident!("extend_{}", field);
+        let replace = format_ident!("replace_{}", field);
+        let extend_doc = format!("Add every item of an iterator to `{}`", field);
+        let replace_doc = format!(
+            "Replace the contents of `{}` with the items of an iterator",
+            field
+        );
+
+        let (generics, item_ty, items) = if collection.kind().is_map() {
+            let (generics, key_ty, value_ty) = self.entry_params(context, &collection);
+            (
+                generics,
+                quote!((#key_ty, #value_ty)),
+                quote!(#field.into_iter().map(|(key, value)| (key.into(), value.into()))),
+            )
+        } else {
+            let element_ty = collection.element_ty();
+            let (generics, item_ty) = if self.attrs.generic {
+                let param = type_param(context.generics, "V");
+                (vec![quote!(#param: Into<#element_ty>)], quote!(#param))
+            } else {
+                (Vec::new(), quote!(impl Into<#element_ty>))
+            };
+            (
+                generics,
+                item_ty,
+                quote!(#field.into_iter().map(Into::into)),
+            )
+        };
+        let (generics, iter_ty) = if self.attrs.generic {
+            let param = type_param(context.generics, "I");
+            (
+                Some(quote!(<#param: IntoIterator<Item = #item_ty>, #(#generics),*>)),
+                quote!(#param),
+            )
+        } else {
+            (None, quote!(impl IntoIterator<Item = #item_ty>))
+        };
+
+        Some(quote! {
+            #[doc = #extend_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #extend #generics(mut self, #field: #iter_ty) -> #output #where_clause {
+                #target.extend(#items);
+                #tail
+            }
+
+            #[doc = #replace_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility fn #replace #generics(mut self, #field: #iter_ty) -> #output #where_clause {
+                #target.clear();
+                #target.extend(#items);
+                #tail
+            }
+        })
+    }
+
+    /// The generics and the key and value parameter types of a setter which
+    /// inserts entries into a map, which are always converted with `into`.
+    ///
+    /// The generics are the bounds of the named type parameters for a
+    /// `generic` setter, without angle brackets, so that they can be combined
+    /// with those of the `extend_` setters.
+    fn entry_params(
+        &self,
+        context: &Context,
+        collection: &CollectionTy,
+    ) -> (Vec<TokenStream2>, TokenStream2, TokenStream2) {
+        let (key_ty, value_ty) = collection.entry_ty();
+        if self.attrs.generic {
+            let key = type_param(context.generics, "K");
+            let value = type_param(context.generics, "V");
+            (
+                vec![quote!(#key: Into<#key_ty>), quote!(#value: Into<#value_ty>)],
+                quote!(#key),
+                quote!(#value),
+            )
+        } else {
+            (
+                Vec::new(),
+                quote!(impl Into<#key_ty>),
+                quote!(impl Into<#value_ty>),
+            )
+        }
+    }
+
+    /// Whether the field has setters which convert the items they add to a
+    /// collection with `into` even without the `into` argument.
+    pub(super) fn converts_items(&self, context: &Context) -> bool {
+        self.attrs.extend
+            || (self.attrs.each.is_some() && self.collection(context, "each").0.kind().is_map())
+    }
+
+    /// The collection type of the field, for arguments which are only valid
+    /// for collections, and the expression which mutably borrows the
+    /// collection.
+    ///
+    /// A collection wrapped in an `Option` is initialised to its default
+    /// when it's first borrowed.
+    fn collection(&self, context: &Context, arg: &str) -> (CollectionTy, TokenStream2) {
+        let field_mut = self.field_mut(context);
+        let collection = match &self.ty {
+            Type::Collection(collection) => Some((collection.clone(), field_mut)),
+            Type::Option(option) => option.inner_collection().map(|collection| {
+                (
+                    collection,
+                    quote!(#field_mut.get_or_insert_with(Default::default)),
+                )
+            }),
+            _ => None,
+        };
+
+        collection.unwrap_or_else(|| {
+            panic!(
+                "'{}' argument is only valid for `Vec`, `VecDeque`, `HashSet`, `BTreeSet`, \
+                 `HashMap` and `BTreeMap` fields, or `Option`s of them",
+                arg
+            )
+        })
+    }
+}
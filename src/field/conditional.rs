@@ -0,0 +1,98 @@
+use super::{Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+impl Field {
+    /// Generate the `_if` and `_some` setters which only call the field's
+    /// setter when a condition holds or a value is given, if the
+    /// `conditional` argument is present.
+    pub(super) fn generate_conditional_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if !self.attrs.conditional {
+            return None;
+        }
+        assert!(
+            !self.attrs.flag,
+            "can't use both 'flag' and 'conditional' on a setter"
+        );
+        assert!(
+            context.transition.is_none(),
+            "'conditional' is not supported for required fields of typestate builders"
+        );
+
+        let Common {
+            forwarded,
+            visibility,
+            constness,
+            output,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+
+        // the generics, names and types of the setter's parameters
+        let tuple = match &self.ty {
+            Type::Tuple(tuple) if self.destructures() => Some(tuple),
+            _ => None,
+        };
+        let (generics, params) = if let Some(tuple) = tuple {
+            (None, self.tuple_params(tuple))
+        } else if let Some((arg_ty, _)) = self.value_ty() {
+            let (generics, param_ty) = if self.attrs.into {
+                self.param_ty(context, &arg_ty)
+            } else {
+                (None, arg_ty)
+            };
+            (generics, vec![(field.clone(), param_ty)])
+        } else {
+            // a stripped bool, whose setter takes no value
+            (None, Vec::new())
+        };
+        let names: Vec<_> = params.iter().map(|(name, _)| name).collect();
+        let tys: Vec<_> = params.iter().map(|(_, ty)| ty).collect();
+
+        let cond_setter = format_ident!("{}_if", field);
+        let cond_doc = format!("Set `{}` if a condition holds", field);
+        let cond = quote! {
+            #[doc = #cond_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility #constness fn #cond_setter #generics(self, cond: bool, #(#names: #tys),*) -> #output #where_clause {
+                if cond {
+                    self.#field(#(#names),*)
+                } else {
+                    self
+                }
+            }
+        };
+
+        if params.is_empty() {
+            return Some(cond);
+        }
+
+        let (value_ty, pattern) = if params.len() == 1 {
+            (quote!(#(#tys)*), quote!(#(#names)*))
+        } else {
+            (quote!((#(#tys),*)), quote!((#(#names),*)))
+        };
+        let some_setter = format_ident!("{}_some", field);
+        let some_doc = format!("Set `{}` if a value is given", field);
+
+        Some(quote! {
+            #cond
+
+            #[doc = #some_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility #constness fn #some_setter #generics(self, #field: Option<#value_ty>) -> #output #where_clause {
+                match #field {
+                    Some(#pattern) => self.#field(#(#names),*),
+                    None => self,
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use super::{Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+impl Field {
+    /// Generate a setter for each of the units in the `units` argument, which
+    /// construct a `Duration` from a number of those units.
+    pub(super) fn generate_unit_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if self.attrs.units.is_empty() {
+            return None;
+        }
+
+        assert!(
+            matches!(self.ty, Type::Duration(_)),
+            "'units' argument is only valid for `Duration` fields"
+        );
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+
+        let setters = self.attrs.units.iter().map(|unit| {
+            let setter = format_ident!("{}_{}", field, unit);
+            let constructor = format_ident!("from_{}", unit);
+            let (arg_ty, description) = match unit.to_string().as_str() {
+                "secs" => (quote!(u64), "seconds"),
+                "millis" => (quote!(u64), "milliseconds"),
+                "micros" => (quote!(u64), "microseconds"),
+                "nanos" => (quote!(u64), "nanoseconds"),
+                "secs_f64" => (quote!(f64), "fractional seconds"),
+                "secs_f32" => (quote!(f32), "fractional seconds"),
+                _ => unreachable!("units are validated when the arguments are parsed"),
+            };
+            let doc = format!("Set `{}` in {}", field, description);
+            let assignment = self.store(
+                context,
+                &quote!(::std::time::Duration::#constructor(#field)),
+            );
+
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility fn #setter(mut self, #field: #arg_ty) -> #output #where_clause {
+                    #assignment
+                    #tail
+                }
+            }
+        });
+
+        Some(setters.collect())
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Common, Context, Field, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+impl Field {
+    /// Generate the `enable_`, `disable_` and `_if` setters of a `bool` or
+    /// `Option<bool>` field, and the `unset_` setter of an `Option<bool>`
+    /// field, if the `flag` argument is present.
+    pub(super) fn generate_flag_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        assert!(
+            self.attrs.flag || self.attrs.negate.is_none(),
+            "'negate' argument is only valid together with 'flag'"
+        );
+        if !self.attrs.flag {
+            return None;
+        }
+
+        let optional = match &self.ty {
+            Type::Bool(_) => false,
+            Type::Option(option) if is_bool(option.inner_ty()) => true,
+            _ => panic!("'flag' argument is only valid for `bool` and `Option<bool>` fields"),
+        };
+
+        let Common {
+            forwarded,
+            visibility,
+            constness,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let wrap = |value: TokenStream2| {
+            let value = if optional {
+                quote!(Some(#value))
+            } else {
+                value
+            };
+            self.store(context, &value)
+        };
+
+        let enable = format_ident!("enable_{}", field);
+        let enable_doc = format!("Set `{}` to `true`", field);
+        let enable_assignment = wrap(quote!(true));
+        let disable = self
+            .attrs
+            .negate
+            .clone()
+            .unwrap_or_else(|| format_ident!("disable_{}", field));
+        let disable_doc = format!("Set `{}` to `false`", field);
+        let disable_assignment = wrap(quote!(false));
+        let cond = format_ident!("{}_if", field);
+        let cond_doc = format!("Set `{}` to the value of a condition", field);
+        let cond_assignment = wrap(quote!(cond));
+
+        let unset = if optional {
+            let unset = format_ident!("unset_{}", field);
+            let doc = format!("Set `{}` to `None`", field);
+            let assignment = self.store(context, &quote!(None));
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                #(#[#forwarded])*
+                #visibility #constness fn #unset(mut self) -> #output #where_clause {
+                    #assignment
+                    #tail
+                }
+            })
+        } else {
+            None
+        };
+
+        Some(quote! {
+            #[doc = #enable_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility #constness fn #enable(mut self) -> #output #where_clause {
+                #enable_assignment
+                #tail
+            }
+
+            #[doc = #disable_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility #constness fn #disable(mut self) -> #output #where_clause {
+                #disable_assignment
+                #tail
+            }
+
+            #[doc = #cond_doc]
+            #[must_use]
+            #(#[#forwarded])*
+            #visibility #constness fn #cond(mut self, cond: bool) -> #output #where_clause {
+                #cond_assignment
+                #tail
+            }
+
+            #unset
+        })
+    }
+}
+
+fn is_bool(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(type_path) if type_path.path.is_ident("bool"))
+}
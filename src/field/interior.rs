@@ -0,0 +1,131 @@
+use super::{ty::AtomicTy, Common, Context, Field, Type};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Field {
+    /// Generate the `set_` setter which writes to a cell or lock through a
+    /// shared reference, if the `interior` argument is present, or the
+    /// setters of an atomic field.
+    ///
+    /// Builders own their values, so their atomic fields only get the usual
+    /// setter.
+    pub(super) fn generate_interior_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if let Type::Atomic(atomic) = &self.ty {
+            if context.builder {
+                assert!(
+                    !(self.attrs.swap || self.attrs.ordering.is_some()),
+                    "'swap' and 'ordering' arguments are not supported for builders"
+                );
+                return None;
+            }
+            return Some(self.generate_atomic_setters(context, common, atomic));
+        }
+
+        assert!(
+            !(self.attrs.swap || self.attrs.ordering.is_some()),
+            "'swap' and 'ordering' arguments are only valid for atomic fields"
+        );
+
+        if !self.attrs.interior {
+            return None;
+        }
+
+        let cell = match &self.ty {
+            Type::Cell(cell) => cell,
+            _ => panic!(
+                "'interior' argument is only valid for `Cell`, `RefCell`, `Mutex` and `RwLock` \
+                 fields"
+            ),
+        };
+        assert!(!context.builder, "'interior' is not supported for builders");
+
+        let Common {
+            forwarded,
+            visibility,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let setter = format_ident!("set_{}", field);
+        let doc = format!("Set `{}` through a shared reference", field);
+        let (generics, arg_ty) = self.param_ty(context, cell.inner_ty());
+        let value = self.converted_arg();
+        let write = cell.kind().write(&quote!(self.#field), &value);
+
+        // not `#[must_use]`, since these setters are usually called for their
+        // side effect rather than chained
+        Some(quote! {
+            #[doc = #doc]
+            #(#[#forwarded])*
+            #visibility fn #setter #generics(&self, #field: #arg_ty) -> &Self #where_clause {
+                #write
+                self
+            }
+        })
+    }
+
+    /// Generate the `set_` setter which stores a value in an atomic field, and
+    /// the `swap_` setter if the `swap` argument is present.
+    fn generate_atomic_setters(
+        &self,
+        context: &Context,
+        common: &Common,
+        atomic: &AtomicTy,
+    ) -> TokenStream2 {
+        let ordering = self
+            .attrs
+            .ordering
+            .clone()
+            .unwrap_or_else(|| Ident::new("SeqCst", Span::call_site()));
+        assert!(
+            ordering != "Acquire" && ordering != "AcqRel",
+            "'ordering' of `{}` must be `Relaxed`, `Release` or `SeqCst`, since it's used to store \
+             values",
+            self.name
+        );
+        let ordering = quote!(::std::sync::atomic::Ordering::#ordering);
+
+        let Common {
+            forwarded,
+            visibility,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let value_ty = atomic.inner_ty();
+        let (generics, arg_ty) = self.param_ty(context, value_ty);
+        let value = self.converted_arg();
+
+        let setter = format_ident!("set_{}", field);
+        let doc = format!("Store a value in `{}`", field);
+        let swap = if self.attrs.swap {
+            let swap = format_ident!("swap_{}", field);
+            let doc = format!("Store a value in `{}`, returning the previous value", field);
+            Some(quote! {
+                #[doc = #doc]
+                #(#[#forwarded])*
+                #visibility fn #swap #generics(&self, #field: #arg_ty) -> #value_ty #where_clause {
+                    self.#field.swap(#value, #ordering)
+                }
+            })
+        } else {
+            None
+        };
+
+        quote! {
+            #[doc = #doc]
+            #(#[#forwarded])*
+            #visibility fn #setter #generics(&self, #field: #arg_ty) -> &Self #where_clause {
+                self.#field.store(#value, #ordering);
+                self
+            }
+
+            #swap
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{type_param, Context, Field, Type};
+use proc_macro2::{Group, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{GenericParam, Ident};
+
+impl Field {
+    /// Generate a setter which changes the type of a field whose type is one
+    /// of the struct's type parameters, rebuilding the struct around the new
+    /// value.
+    pub(super) fn generate_retype_setter(
+        &self,
+        context: &Context,
+        attrs: &TokenStream2,
+    ) -> TokenStream2 {
+        assert!(
+            !(self.attrs.into
+                || self.attrs.strip
+                || self.attrs.constant
+                || self.attrs.outline
+                || self.attrs.generic
+                || self.attrs.reset
+                || self.attrs.wrap
+                || self.attrs.conditional
+                || self.attrs.try_into),
+            "'retype' can't be combined with 'into', 'strip', 'const', 'outline', 'generic', \
+             'reset', 'wrap', 'conditional' or 'try_into'"
+        );
+
+        let param = match &self.ty {
+            Type::Other(syn::Type::Path(type_path)) if type_path.qself.is_none() => {
+                type_path.path.get_ident()
+            }
+            _ => None,
+        }
+        .filter(|ident| context.generics.type_params().any(|param| param.ident == **ident))
+        .expect("'retype' argument is only valid for fields whose type is a type parameter of the struct");
+
+        let field = &self.name;
+        let others: Vec<_> = context
+            .fields
+            .iter()
+            .filter(|(name, _)| name != field)
+            .map(|(name, ty)| {
+                assert!(
+                    !contains_ident(ty.to_token_stream(), param),
+                    "'retype' requires that no other field's type uses `{}`",
+                    param
+                );
+                name
+            })
+            .collect();
+
+        let new_param = type_param(context.generics, "U");
+        let retype = |tokens: TokenStream2| replace_ident(tokens, param, &new_param);
+
+        let name = context.name;
+        let ty_args = context.generics.params.iter().map(|generic| match generic {
+            GenericParam::Type(ty) => retype(ty.ident.to_token_stream()),
+            GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_token_stream(),
+            GenericParam::Const(constant) => constant.ident.to_token_stream(),
+        });
+        let param_bounds = context
+            .generics
+            .type_params()
+            .find(|ty| ty.ident == *param)
+            .map(|ty| &ty.bounds)
+            .filter(|bounds| !bounds.is_empty())
+            .map(|bounds| retype(quote!(: #bounds)));
+
+        // predicates on the old type parameter must also hold for the new one
+        let predicates = context
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .map(ToTokens::to_token_stream)
+            .filter(|predicate| contains_ident(predicate.clone(), param))
+            .map(retype)
+            .chain(self.attrs.bounds.iter().map(ToTokens::to_token_stream))
+            .collect::<Vec<_>>();
+        let where_clause = if predicates.is_empty() {
+            None
+        } else {
+            Some(quote!(where #(#predicates),*))
+        };
+
+        let visibility = &self.attrs.visibility;
+
+        quote! {
+            #attrs
+            #visibility fn #field<#new_param #param_bounds>(self, #field: #new_param) -> #name<#(#ty_args),*> #where_clause {
+                #name {
+                    #field,
+                    #(#others: self.#others,)*
+                }
+            }
+        }
+    }
+}
+
+/// Check whether a token stream contains the given identifier.
+pub fn contains_ident(tokens: TokenStream2, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Replace every occurrence of an identifier in a token stream.
+fn replace_ident(tokens: TokenStream2, from: &Ident, to: &Ident) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == *from => TokenTree::Ident(to.clone()),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_ident(group.stream(), from, to));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced)
+            }
+            tt => tt,
+        })
+        .collect()
+}
@@ -0,0 +1,52 @@
+use super::{type_param, Common, Context, Field};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+impl Field {
+    /// Generate the `try_` setter which converts its argument with `TryInto`
+    /// and returns the conversion error if it fails, if the `try_into`
+    /// argument is present.
+    ///
+    /// The argument's type is always a named type parameter, since the
+    /// setter's return type names its `Error`.
+    pub(super) fn generate_try_into_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+    ) -> Option<TokenStream2> {
+        if !self.attrs.try_into {
+            return None;
+        }
+
+        let (arg_ty, wrappers) = self
+            .value_ty()
+            .expect("'try_into' argument isn't supported for stripped `bool` fields");
+
+        let Common {
+            forwarded,
+            visibility,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+        let method = format_ident!("try_{}", field);
+        let doc = format!("Set `{}` from a value which may fail to convert", field);
+        let param = type_param(context.generics, "V");
+        let value = wrappers
+            .iter()
+            .fold(quote!(#field), |value, wrapper| quote!(#wrapper(#value)));
+        let assignment = self.store(context, &value);
+
+        Some(quote! {
+            #[doc = #doc]
+            #(#[#forwarded])*
+            #visibility fn #method<#param: ::std::convert::TryInto<#arg_ty>>(mut self, #field: #param) -> Result<#output, #param::Error> #where_clause {
+                let #field = ::std::convert::TryInto::try_into(#field)?;
+                #assignment
+                Ok(#tail)
+            }
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use super::{ty::TupleTy, Common, Context, Field};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Field {
+    /// Whether the setter of a tuple field takes one parameter per element
+    /// rather than the whole tuple, which is the case if the `destructure` or
+    /// `args` arguments are present.
+    pub(super) fn destructures(&self) -> bool {
+        self.attrs.destructure || !self.attrs.arg_names.is_empty()
+    }
+
+    /// Generate the setter of a tuple field, which takes one parameter per
+    /// element of the tuple.
+    pub(super) fn generate_tuple_setter(
+        &self,
+        context: &Context,
+        common: &Common,
+        tuple: &TupleTy,
+    ) -> TokenStream2 {
+        assert!(
+            !(self.attrs.strip || self.attrs.outline || self.attrs.generic),
+            "'strip', 'outline' and 'generic' arguments aren't supported for destructured tuple fields"
+        );
+
+        let Common {
+            attrs,
+            visibility,
+            constness,
+            output,
+            tail,
+            where_clause,
+            ..
+        } = common;
+        let field = &self.name;
+
+        let (names, params): (Vec<_>, Vec<_>) = self.tuple_params(tuple).into_iter().unzip();
+        let params = names
+            .iter()
+            .zip(&params)
+            .map(|(name, ty)| quote!(#name: #ty));
+        let values = names.iter().map(|name| {
+            if self.attrs.into {
+                quote!(#name.into())
+            } else {
+                quote!(#name)
+            }
+        });
+        let assignment = self.store(context, &quote!((#(#values,)*)));
+
+        quote! {
+            #attrs
+            #visibility #constness fn #field(mut self, #(#params),*) -> #output #where_clause {
+                #assignment
+                #tail
+            }
+        }
+    }
+
+    /// The names and types of the parameters of a tuple field's setter, one
+    /// per element of the tuple.
+    pub(super) fn tuple_params(&self, tuple: &TupleTy) -> Vec<(Ident, TokenStream2)> {
+        let field = &self.name;
+        let elems: Vec<_> = tuple.elems().collect();
+        let names: Vec<_> = if self.attrs.arg_names.is_empty() {
+            (0..elems.len())
+                .map(|i| format_ident!("{}_{}", field, i))
+                .collect()
+        } else {
+            assert!(
+                self.attrs.arg_names.len() == elems.len(),
+                "'args' argument of `{}` must name each of its {} elements",
+                field,
+                elems.len()
+            );
+            self.attrs.arg_names.clone()
+        };
+
+        names
+            .into_iter()
+            .zip(elems)
+            .map(|(name, ty)| {
+                let param_ty = if self.attrs.into {
+                    quote!(impl Into<#ty>)
+                } else {
+                    quote!(#ty)
+                };
+                (name, param_ty)
+            })
+            .collect()
+    }
+}
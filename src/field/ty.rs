@@ -1,20 +1,57 @@
-use quote::ToTokens;
-use syn::{GenericArgument, PathArguments};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{GenericArgument, Ident, PathArguments, TypeParamBound};
 
 #[derive(Debug)]
 pub enum Type {
     Bool(syn::Type),
+    Duration(syn::Type),
     Option(OptionTy),
+    Collection(CollectionTy),
+    Buffer(BufferTy),
+    Pointer(PointerTy),
+    Cell(CellTy),
+    Atomic(AtomicTy),
+    Tuple(TupleTy),
+    Array(ArrayTy),
     Other(syn::Type),
 }
 
 impl From<syn::Type> for Type {
     fn from(ty: syn::Type) -> Self {
-        if let syn::Type::Path(type_path) = &ty {
+        if let syn::Type::Tuple(tuple) = &ty {
+            if !tuple.elems.is_empty() {
+                return Type::Tuple(TupleTy { ty });
+            }
+        } else if let syn::Type::Array(_) = &ty {
+            return Type::Array(ArrayTy { ty });
+        } else if let syn::Type::Path(type_path) = &ty {
             if type_path.path.segments.first().unwrap().ident == "Option" {
                 return Type::Option(OptionTy { ty });
             } else if type_path.path.is_ident("bool") {
                 return Type::Bool(ty);
+            } else if is_duration(&type_path.path) {
+                return Type::Duration(ty);
+            } else if let Some(kind) =
+                CollectionKind::from_ident(&type_path.path.segments.last().unwrap().ident)
+            {
+                return Type::Collection(CollectionTy { ty, kind });
+            } else if let Some(kind) =
+                BufferKind::from_ident(&type_path.path.segments.last().unwrap().ident)
+            {
+                return Type::Buffer(BufferTy { ty, kind });
+            } else if let Some(kind) =
+                PointerKind::from_ident(&type_path.path.segments.last().unwrap().ident)
+            {
+                return Type::Pointer(PointerTy { ty, kind });
+            } else if let Some(kind) =
+                CellKind::from_ident(&type_path.path.segments.last().unwrap().ident)
+            {
+                return Type::Cell(CellTy { ty, kind });
+            } else if let Some(value_ty) =
+                AtomicTy::value_ty(&type_path.path.segments.last().unwrap().ident)
+            {
+                return Type::Atomic(AtomicTy { ty, value_ty });
             }
         }
 
@@ -22,10 +59,35 @@ impl From<syn::Type> for Type {
     }
 }
 
+impl Type {
+    /// Whether the type obviously needs to be dropped, because it owns a
+    /// collection, string or pointer. Other types may still need dropping.
+    pub fn needs_drop(&self) -> bool {
+        let needs_drop = |ty: &syn::Type| Type::from(ty.clone()).needs_drop();
+        match self {
+            Type::Collection(_) | Type::Buffer(_) | Type::Pointer(_) => true,
+            Type::Option(option) => needs_drop(option.inner_ty()),
+            Type::Tuple(tuple) => tuple.elems().any(needs_drop),
+            Type::Array(array) => needs_drop(array.element_ty()),
+            _ => false,
+        }
+    }
+}
+
 impl ToTokens for Type {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
         let ty = match self {
-            Type::Bool(t) | Type::Option(OptionTy { ty: t }) | Type::Other(t) => t,
+            Type::Bool(t)
+            | Type::Duration(t)
+            | Type::Option(OptionTy { ty: t })
+            | Type::Collection(CollectionTy { ty: t, .. })
+            | Type::Buffer(BufferTy { ty: t, .. })
+            | Type::Pointer(PointerTy { ty: t, .. })
+            | Type::Cell(CellTy { ty: t, .. })
+            | Type::Atomic(AtomicTy { ty: t, .. })
+            | Type::Tuple(TupleTy { ty: t })
+            | Type::Array(ArrayTy { ty: t })
+            | Type::Other(t) => t,
         };
 
         ty.to_tokens(tokens);
@@ -52,6 +114,407 @@ impl OptionTy {
 
         panic!()
     }
+
+    /// The pointer wrapped by the `Option`, if any
+    pub fn inner_pointer(&self) -> Option<PointerTy> {
+        match Type::from(self.inner_ty().clone()) {
+            Type::Pointer(pointer) => Some(pointer),
+            _ => None,
+        }
+    }
+
+    /// The collection wrapped by the `Option`, if any
+    pub fn inner_collection(&self) -> Option<CollectionTy> {
+        match Type::from(self.inner_ty().clone()) {
+            Type::Collection(collection) => Some(collection),
+            _ => None,
+        }
+    }
+}
+
+/// A standard library collection which elements can be added to one at a time
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+pub struct CollectionTy {
+    ty: syn::Type,
+    kind: CollectionKind,
+}
+
+impl CollectionTy {
+    pub fn kind(&self) -> CollectionKind {
+        self.kind
+    }
+
+    /// The type of the elements of the collection
+    pub fn element_ty(&self) -> &syn::Type {
+        type_args(&self.ty)
+            .next()
+            .expect("collection types must have an element type parameter")
+    }
+
+    /// The types of the keys and values of a map
+    pub fn entry_ty(&self) -> (&syn::Type, &syn::Type) {
+        let mut args = type_args(&self.ty);
+        args.next()
+            .zip(args.next())
+            .expect("map types must have key and value type parameters")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollectionKind {
+    Vec,
+    VecDeque,
+    HashSet,
+    BTreeSet,
+    HashMap,
+    BTreeMap,
+}
+
+impl CollectionKind {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        if ident == "Vec" {
+            Some(Self::Vec)
+        } else if ident == "VecDeque" {
+            Some(Self::VecDeque)
+        } else if ident == "HashSet" {
+            Some(Self::HashSet)
+        } else if ident == "BTreeSet" {
+            Some(Self::BTreeSet)
+        } else if ident == "HashMap" {
+            Some(Self::HashMap)
+        } else if ident == "BTreeMap" {
+            Some(Self::BTreeMap)
+        } else {
+            None
+        }
+    }
+
+    /// The method which adds a single element to the collection
+    pub fn add_method(self) -> Ident {
+        let method = match self {
+            Self::Vec => "push",
+            Self::VecDeque => "push_back",
+            Self::HashSet | Self::BTreeSet | Self::HashMap | Self::BTreeMap => "insert",
+        };
+        Ident::new(method, Span::call_site())
+    }
+
+    /// Whether the collection maps keys to values
+    pub fn is_map(self) -> bool {
+        matches!(self, Self::HashMap | Self::BTreeMap)
+    }
+}
+
+/// An owned string or path which can be appended to
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct BufferTy {
+    ty: syn::Type,
+    kind: BufferKind,
+}
+
+impl BufferTy {
+    pub fn kind(&self) -> BufferKind {
+        self.kind
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BufferKind {
+    String,
+    OsString,
+    PathBuf,
+}
+
+impl BufferKind {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        if ident == "String" {
+            Some(Self::String)
+        } else if ident == "OsString" {
+            Some(Self::OsString)
+        } else if ident == "PathBuf" {
+            Some(Self::PathBuf)
+        } else {
+            None
+        }
+    }
+
+    /// The method which appends to the buffer
+    pub fn append_method(self) -> Ident {
+        let method = match self {
+            Self::String => "push_str",
+            Self::OsString | Self::PathBuf => "push",
+        };
+        Ident::new(method, Span::call_site())
+    }
+
+    /// The borrowed type which can be appended to the buffer
+    pub fn borrowed_ty(self) -> TokenStream {
+        match self {
+            Self::String => quote!(str),
+            Self::OsString => quote!(::std::ffi::OsStr),
+            Self::PathBuf => quote!(::std::path::Path),
+        }
+    }
+}
+
+/// A smart pointer which a value can be wrapped in
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+pub struct PointerTy {
+    ty: syn::Type,
+    kind: PointerKind,
+}
+
+impl PointerTy {
+    pub fn kind(&self) -> PointerKind {
+        self.kind
+    }
+
+    /// Whether the pointer is to a trait object
+    pub fn is_dyn(&self) -> bool {
+        matches!(self.pointee_ty(), syn::Type::TraitObject(_))
+    }
+
+    /// The type of the value which is wrapped in the pointer, which is an
+    /// `impl Trait` type for trait objects
+    pub fn owned_ty(&self) -> TokenStream {
+        let pointee_ty = self.pointee_ty();
+        if let syn::Type::TraitObject(trait_object) = pointee_ty {
+            let bounds = &trait_object.bounds;
+            // trait objects in fields are `'static` unless they have another
+            // lifetime bound
+            let lifetime = if bounds
+                .iter()
+                .any(|bound| matches!(bound, TypeParamBound::Lifetime(_)))
+            {
+                None
+            } else {
+                Some(quote!(+ 'static))
+            };
+            return quote!(impl #bounds #lifetime);
+        }
+
+        match self.kind {
+            PointerKind::Cow => quote!(<#pointee_ty as ::std::borrow::ToOwned>::Owned),
+            PointerKind::Box | PointerKind::Rc | PointerKind::Arc => quote!(#pointee_ty),
+        }
+    }
+
+    fn pointee_ty(&self) -> &syn::Type {
+        type_args(&self.ty)
+            .next()
+            .expect("pointer types must have a type parameter")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerKind {
+    Box,
+    Rc,
+    Arc,
+    Cow,
+}
+
+impl PointerKind {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        if ident == "Box" {
+            Some(Self::Box)
+        } else if ident == "Rc" {
+            Some(Self::Rc)
+        } else if ident == "Arc" {
+            Some(Self::Arc)
+        } else if ident == "Cow" {
+            Some(Self::Cow)
+        } else {
+            None
+        }
+    }
+
+    /// The function which wraps an owned value in the pointer
+    pub fn constructor(self) -> TokenStream {
+        match self {
+            Self::Box => quote!(::std::boxed::Box::new),
+            Self::Rc => quote!(::std::rc::Rc::new),
+            Self::Arc => quote!(::std::sync::Arc::new),
+            Self::Cow => quote!(::std::borrow::Cow::Owned),
+        }
+    }
+}
+
+/// A cell or lock which can be written to through a shared reference
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct CellTy {
+    ty: syn::Type,
+    kind: CellKind,
+}
+
+impl CellTy {
+    pub fn kind(&self) -> CellKind {
+        self.kind
+    }
+
+    /// The type of the value in the cell
+    pub fn inner_ty(&self) -> &syn::Type {
+        type_args(&self.ty)
+            .next()
+            .expect("cell types must have a type parameter")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellKind {
+    Cell,
+    RefCell,
+    Mutex,
+    RwLock,
+}
+
+impl CellKind {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        if ident == "Cell" {
+            Some(Self::Cell)
+        } else if ident == "RefCell" {
+            Some(Self::RefCell)
+        } else if ident == "Mutex" {
+            Some(Self::Mutex)
+        } else if ident == "RwLock" {
+            Some(Self::RwLock)
+        } else {
+            None
+        }
+    }
+
+    /// The statement which writes a value to a cell through a shared
+    /// reference.
+    ///
+    /// Poisoned locks are written to regardless, since the new value replaces
+    /// whatever was left by the panicking thread.
+    pub fn write(self, cell: &TokenStream, value: &TokenStream) -> TokenStream {
+        match self {
+            Self::Cell => quote!(#cell.set(#value);),
+            Self::RefCell => quote!(*#cell.borrow_mut() = #value;),
+            Self::Mutex => {
+                quote!(*#cell.lock().unwrap_or_else(::std::sync::PoisonError::into_inner) = #value;)
+            }
+            Self::RwLock => {
+                quote!(*#cell.write().unwrap_or_else(::std::sync::PoisonError::into_inner) = #value;)
+            }
+        }
+    }
+}
+
+/// One of the standard library's atomic integer or `bool` types
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct AtomicTy {
+    ty: syn::Type,
+
+    /// The type of the value stored in the atomic
+    value_ty: Ident,
+}
+
+impl AtomicTy {
+    /// The type of the value stored in an atomic type with the given name
+    fn value_ty(ident: &Ident) -> Option<Ident> {
+        const VALUE_TYPES: &[&str] = &[
+            "bool", "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+        ];
+
+        let name = ident.to_string();
+        let value_ty = name.strip_prefix("Atomic")?.to_lowercase();
+        if VALUE_TYPES.contains(&value_ty.as_str()) {
+            Some(Ident::new(&value_ty, ident.span()))
+        } else {
+            None
+        }
+    }
+
+    pub fn inner_ty(&self) -> &Ident {
+        &self.value_ty
+    }
+}
+
+/// A tuple with at least one element
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct TupleTy {
+    ty: syn::Type,
+}
+
+impl TupleTy {
+    /// The types of the elements of the tuple
+    pub fn elems(&self) -> impl Iterator<Item = &syn::Type> {
+        match &self.ty {
+            syn::Type::Tuple(tuple) => tuple.elems.iter(),
+            _ => unreachable!("tuple types are only constructed from tuples"),
+        }
+    }
+}
+
+/// A fixed-size array
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct ArrayTy {
+    ty: syn::Type,
+}
+
+impl ArrayTy {
+    fn array(&self) -> &syn::TypeArray {
+        match &self.ty {
+            syn::Type::Array(array) => array,
+            _ => unreachable!("array types are only constructed from arrays"),
+        }
+    }
+
+    /// The type of the elements of the array
+    pub fn element_ty(&self) -> &syn::Type {
+        &self.array().elem
+    }
+
+    /// The length of the array
+    pub fn len(&self) -> &syn::Expr {
+        &self.array().len
+    }
+}
+
+/// Whether a path names the standard library's `Duration`, rather than
+/// another crate's type of the same name.
+fn is_duration(path: &syn::Path) -> bool {
+    let idents: Vec<_> = path.segments.iter().map(|segment| &segment.ident).collect();
+    let is_std = match idents.as_slice() {
+        [ty] => path.leading_colon.is_none() && *ty == "Duration",
+        [krate, module, ty] => {
+            (*krate == "std" || *krate == "core") && *module == "time" && *ty == "Duration"
+        }
+        _ => false,
+    };
+    is_std
+        && path
+            .segments
+            .iter()
+            .all(|segment| segment.arguments.is_empty())
+}
+
+/// The type arguments of the last segment of a type's path
+fn type_args(ty: &syn::Type) -> impl Iterator<Item = &syn::Type> {
+    let arguments = match ty {
+        syn::Type::Path(type_path) => Some(&type_path.path.segments.last().unwrap().arguments),
+        _ => None,
+    };
+    arguments
+        .into_iter()
+        .filter_map(|arguments| match arguments {
+            PathArguments::AngleBracketed(arguments) => Some(&arguments.args),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
 }
 
 #[cfg(test)]
@@ -69,11 +532,48 @@ mod tests {
     #[test_case("u32" => "other")]
     #[test_case("Option<u32>" => "option")]
     #[test_case("bool" => "bool")]
+    #[test_case("Vec<u32>" => "collection")]
+    #[test_case("std::collections::HashSet<u32>" => "collection")]
+    #[test_case("BTreeMap<String, u32>" => "collection")]
+    #[test_case("String" => "buffer")]
+    #[test_case("std::path::PathBuf" => "buffer")]
+    #[test_case("Box<u32>" => "pointer")]
+    #[test_case("Cow<'a, str>" => "pointer")]
+    #[test_case("Arc<dyn Fn() + Send>" => "pointer")]
+    #[test_case("std::sync::Mutex<u32>" => "cell")]
+    #[test_case("AtomicU64" => "atomic")]
+    #[test_case("std::time::Duration" => "duration")]
+    #[test_case("::core::time::Duration" => "duration")]
+    #[test_case("Duration" => "duration")]
+    #[test_case("chrono::Duration" => "other")]
+    #[test_case("time::Duration" => "other")]
+    #[test_case("(u32, u32)" => "tuple")]
+    #[test_case("()" => "other")]
+    #[test_case("[u8; 4]" => "array")]
+    #[test_case("AtomicPtr<u8>" => "other")]
     fn parse(input: &str) -> &str {
         match parse_input(input) {
             Type::Bool(_) => "bool",
+            Type::Duration(_) => "duration",
             Type::Option(_) => "option",
+            Type::Collection(_) => "collection",
+            Type::Buffer(_) => "buffer",
+            Type::Pointer(_) => "pointer",
+            Type::Cell(_) => "cell",
+            Type::Atomic(_) => "atomic",
+            Type::Tuple(_) => "tuple",
+            Type::Array(_) => "array",
             Type::Other(_) => "other",
         }
     }
+
+    #[test_case("u32" => false)]
+    #[test_case("Option<u32>" => false)]
+    #[test_case("Option<String>" => true)]
+    #[test_case("Box<u32>" => true)]
+    #[test_case("(u8, Vec<u8>)" => true)]
+    #[test_case("[String; 2]" => true)]
+    fn needs_drop(input: &str) -> bool {
+        parse_input(input).needs_drop()
+    }
 }
@@ -0,0 +1,76 @@
+use super::{
+    ty::{PointerKind, PointerTy},
+    Field, Type,
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+impl Field {
+    /// The type accepted by the setter of a field with the `wrap` argument,
+    /// and the functions which wrap it in the field's pointer type, innermost
+    /// first.
+    pub(super) fn wrapped_ty(&self) -> (TokenStream2, Vec<TokenStream2>) {
+        let pointer = match &self.ty {
+            Type::Pointer(pointer) => Some((pointer.clone(), false)),
+            Type::Option(option) => option
+                .inner_pointer()
+                .filter(|pointer| pointer.kind() == PointerKind::Box)
+                .map(|pointer| (pointer, true)),
+            _ => None,
+        };
+        let (pointer, optional) = pointer.expect(
+            "'wrap' argument is only valid for `Box`, `Rc`, `Arc`, `Cow` and `Option<Box>` fields",
+        );
+
+        self.wrap_pointer(&pointer, optional)
+    }
+
+    /// The type accepted by the setter of a `Box<dyn ..>` or `Arc<dyn ..>`
+    /// field, or of an `Option` of one with the `strip` argument, which is
+    /// wrapped even without the `wrap` argument, unless the `raw` argument is
+    /// present.
+    pub(super) fn dyn_ty(&self) -> Option<(TokenStream2, Vec<TokenStream2>)> {
+        let pointer = match (self.attrs.strip, &self.ty) {
+            (false, Type::Pointer(pointer)) if pointer.is_dyn() => Some((pointer.clone(), false)),
+            (true, Type::Option(option)) => option
+                .inner_pointer()
+                .filter(PointerTy::is_dyn)
+                .map(|pointer| (pointer, true)),
+            _ => None,
+        };
+
+        if self.attrs.raw {
+            assert!(
+                pointer.is_some(),
+                "'raw' argument is only valid for trait object fields"
+            );
+            return None;
+        }
+        pointer.map(|(pointer, optional)| self.wrap_pointer(&pointer, optional))
+    }
+
+    /// The type wrapped by a pointer, and the functions which wrap it in the
+    /// pointer and then, if `optional`, in `Some`.
+    fn wrap_pointer(
+        &self,
+        pointer: &PointerTy,
+        optional: bool,
+    ) -> (TokenStream2, Vec<TokenStream2>) {
+        if pointer.is_dyn() {
+            assert!(
+                !self.attrs.into,
+                "'into' argument isn't supported for trait object fields"
+            );
+            assert!(
+                !self.attrs.constant,
+                "'const' argument isn't supported for trait object fields"
+            );
+        }
+
+        let mut wrappers = vec![pointer.kind().constructor()];
+        if optional {
+            wrappers.push(quote!(Some));
+        }
+        (pointer.owned_ty(), wrappers)
+    }
+}
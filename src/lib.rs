@@ -18,3 +18,12 @@ pub fn derive_fluent_setters(input: TokenStream) -> TokenStream {
 
     data.generate_impl().into()
 }
+
+#[proc_macro_derive(FluentBuilder, attributes(set))]
+pub fn derive_fluent_builder(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+
+    let data = Data::from_derive_input(ast);
+
+    data.generate_builder().into()
+}
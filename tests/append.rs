@@ -0,0 +1,44 @@
+use fluent_setters::FluentSetters;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Command {
+    #[set(append = "push_line")]
+    script: String,
+
+    #[set(append = "push_flags")]
+    flags: OsString,
+
+    #[set(append = "join")]
+    dir: PathBuf,
+
+    #[set(append = "push_env", generic)]
+    env: String,
+}
+
+#[test]
+fn append() {
+    let command = Command::default()
+        .push_line("echo ")
+        .push_line(String::from("hello"))
+        .push_flags("-v")
+        .push_flags(OsString::from("x"))
+        .join("/usr")
+        .join(Path::new("bin"));
+
+    assert_eq!(command.script, "echo hello");
+    assert_eq!(command.flags, "-vx");
+    assert_eq!(command.dir, Path::new("/usr/bin"));
+}
+
+#[test]
+fn generic_append() {
+    let command = Command::default()
+        .push_env::<&str>("A=1 ")
+        .push_env::<String>(String::from("B=2"));
+
+    assert_eq!(command.env, "A=1 B=2");
+}
@@ -0,0 +1,45 @@
+use fluent_setters::FluentSetters;
+
+/// A type which doesn't implement `Clone`
+#[derive(Debug, Default, PartialEq)]
+struct Brush(u8);
+
+#[derive(Debug, Default, FluentSetters)]
+struct Palette {
+    #[set(index(fill), into)]
+    colours: [u32; 4],
+
+    #[set(index(fill))]
+    names: [String; 2],
+
+    #[set(index)]
+    brushes: [Brush; 2],
+}
+
+#[test]
+fn at() {
+    let palette = Palette::default()
+        .colours_at(1, 0xff_u8)
+        .names_at(0, "red".to_string())
+        .brushes_at(1, Brush(3));
+
+    assert_eq!(palette.colours, [0, 0xff, 0, 0]);
+    assert_eq!(palette.names, ["red".to_string(), String::new()]);
+    assert_eq!(palette.brushes, [Brush(0), Brush(3)]);
+}
+
+#[test]
+#[should_panic]
+fn at_out_of_bounds() {
+    let _ = Palette::default().colours_at(4, 0_u8);
+}
+
+#[test]
+fn fill() {
+    let palette = Palette::default()
+        .colours_fill(7_u8)
+        .names_fill("x".to_string());
+
+    assert_eq!(palette.colours, [7; 4]);
+    assert_eq!(palette.names, ["x", "x"]);
+}
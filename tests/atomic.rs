@@ -0,0 +1,44 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Toggles {
+    #[set(ordering = "Release", swap)]
+    enabled: AtomicBool,
+
+    #[set(into)]
+    limit: AtomicU64,
+}
+
+#[test]
+fn store() {
+    let toggles = Toggles::default();
+    toggles.set_enabled(true).set_limit(10_u32);
+
+    assert!(toggles.enabled.load(Ordering::Acquire));
+    assert_eq!(toggles.limit.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn swap() {
+    let toggles = Toggles::default();
+
+    assert!(!toggles.swap_enabled(true));
+    assert!(toggles.swap_enabled(false));
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Worker {
+    #[set]
+    running: AtomicBool,
+}
+
+#[test]
+fn builder_atomic() {
+    let worker = Worker::builder()
+        .running(AtomicBool::new(true))
+        .build()
+        .unwrap();
+
+    assert!(worker.running.load(Ordering::SeqCst));
+}
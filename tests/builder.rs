@@ -0,0 +1,74 @@
+use fluent_setters::FluentBuilder;
+
+#[derive(Debug, PartialEq, FluentBuilder)]
+struct Config {
+    #[set(required, into)]
+    name: String,
+
+    #[set(required)]
+    port: u16,
+
+    #[set(strip)]
+    timeout: Option<u64>,
+
+    #[set(strip)]
+    verbose: bool,
+
+    #[set(default = "3")]
+    retries: u32,
+}
+
+#[test]
+fn build() {
+    let config = Config::builder()
+        .name("server")
+        .port(8080)
+        .verbose()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "server".to_string(),
+            port: 8080,
+            timeout: None,
+            verbose: true,
+            retries: 3,
+        }
+    );
+}
+
+#[test]
+fn missing_required_fields() {
+    let error = Config::builder().timeout(10).build().unwrap_err();
+
+    assert_eq!(error.missing_fields(), &["name", "port"]);
+    assert_eq!(error.to_string(), "missing required fields: name, port");
+}
+
+/// A type without a `Default` implementation
+#[derive(Debug, PartialEq)]
+struct Task(&'static str);
+
+#[derive(Debug, PartialEq, FluentBuilder)]
+struct Job<T, U> {
+    #[set(strip)]
+    task: Option<T>,
+
+    #[set]
+    payload: U,
+}
+
+#[test]
+fn build_generic() {
+    let job = Job::builder().task(Task("backup")).build().unwrap();
+
+    assert_eq!(
+        job,
+        Job {
+            task: Some(Task("backup")),
+            payload: String::new(),
+        }
+    );
+}
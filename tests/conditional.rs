@@ -0,0 +1,54 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Query {
+    #[set(strip, into, conditional)]
+    filter: Option<String>,
+
+    #[set(destructure, conditional)]
+    range: (u32, u32),
+
+    #[set(strip, conditional)]
+    distinct: bool,
+}
+
+#[test]
+fn conditional() {
+    let query = Query::default()
+        .filter_if(false, "name")
+        .range_if(true, 1, 10)
+        .distinct_if(true);
+    assert_eq!(query.filter, None);
+    assert_eq!(query.range, (1, 10));
+    assert!(query.distinct);
+
+    let query = query.filter_if(true, "name").range_if(false, 5, 6);
+    assert_eq!(query.filter.as_deref(), Some("name"));
+    assert_eq!(query.range, (1, 10));
+}
+
+#[test]
+fn some() {
+    let query = Query::default().filter_some(Some("name")).range_some(None);
+    assert_eq!(query.filter.as_deref(), Some("name"));
+    assert_eq!(query.range, (0, 0));
+
+    let query = query.filter_some(None::<String>).range_some(Some((2, 3)));
+    assert_eq!(query.filter.as_deref(), Some("name"));
+    assert_eq!(query.range, (2, 3));
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Connection {
+    #[set(conditional, default = "5432")]
+    port: u16,
+}
+
+#[test]
+fn builder_conditional() {
+    let connection = Connection::builder().port_some(None).build().unwrap();
+    assert_eq!(connection.port, 5432);
+
+    let connection = Connection::builder().port_if(true, 6543).build().unwrap();
+    assert_eq!(connection.port, 6543);
+}
@@ -0,0 +1,45 @@
+use fluent_setters::FluentSetters;
+
+#[derive(Debug, PartialEq, FluentSetters)]
+struct Config {
+    #[set(const)]
+    retries: u32,
+
+    #[set(const, strip)]
+    verbose: bool,
+
+    #[set(const, strip)]
+    limit: Option<u64>,
+}
+
+impl Config {
+    const fn new() -> Self {
+        Self {
+            retries: 0,
+            verbose: false,
+            limit: None,
+        }
+    }
+}
+
+const DEFAULT: Config = Config::new().retries(3).verbose().limit(10);
+
+static STATIC: Config = Config::new().limit(5);
+
+#[test]
+fn const_chain() {
+    assert_eq!(
+        DEFAULT,
+        Config {
+            retries: 3,
+            verbose: true,
+            limit: Some(10),
+        }
+    );
+}
+
+#[test]
+fn static_chain() {
+    assert_eq!(STATIC.limit, Some(5));
+    assert_eq!(STATIC.retries, 0);
+}
@@ -0,0 +1,62 @@
+use fluent_setters::FluentSetters;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, FluentSetters)]
+struct Config {
+    #[set(required, into)]
+    name: String,
+
+    #[set(required)]
+    port: u16,
+
+    #[set(default = "Duration::from_secs(5)")]
+    timeout: Duration,
+
+    #[set]
+    retries: u32,
+}
+
+#[test]
+fn new() {
+    let config = Config::new("server", 8080).retries(3);
+
+    assert_eq!(
+        config,
+        Config {
+            name: "server".to_string(),
+            port: 8080,
+            timeout: Duration::from_secs(5),
+            retries: 3,
+        }
+    );
+}
+
+/// A type without a `Default` implementation
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+#[derive(Debug, PartialEq, FluentSetters)]
+struct Slot<T, U> {
+    #[set(required)]
+    handle: T,
+
+    #[set(strip)]
+    backup: Option<T>,
+
+    #[set]
+    data: U,
+}
+
+#[test]
+fn new_generic() {
+    let slot = Slot::new(Handle(1)).data(vec![1_u8]);
+
+    assert_eq!(
+        slot,
+        Slot {
+            handle: Handle(1),
+            backup: None,
+            data: vec![1],
+        }
+    );
+}
@@ -0,0 +1,67 @@
+use fluent_setters::FluentSetters;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, FluentSetters)]
+#[set(impl_default)]
+struct Config {
+    #[set(into, default = r#""localhost".to_string()"#)]
+    host: String,
+
+    #[set(default = "8080")]
+    port: u16,
+
+    #[set(default = "Duration::from_secs(5)")]
+    timeout: Duration,
+
+    retries: u32,
+}
+
+#[test]
+fn impl_default() {
+    assert_eq!(
+        Config::default(),
+        Config {
+            host: "localhost".to_string(),
+            port: 8080,
+            timeout: Duration::from_secs(5),
+            retries: 0,
+        }
+    );
+}
+
+/// A type without a `Default` implementation
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+#[derive(Debug, PartialEq, FluentSetters)]
+#[set(impl_default)]
+struct Slot<T> {
+    #[set]
+    value: T,
+
+    #[set(strip)]
+    fallback: Option<T>,
+}
+
+#[test]
+fn impl_default_generic() {
+    assert_eq!(
+        Slot::default().fallback(1_u8),
+        Slot {
+            value: 0,
+            fallback: Some(1),
+        }
+    );
+
+    // the setters don't need the field types to implement `Default`
+    let slot = Slot {
+        value: Handle(1),
+        fallback: None,
+    };
+    assert_eq!(slot.value(Handle(2)).value, Handle(2));
+}
+
+#[test]
+fn override_default() {
+    assert_eq!(Config::default().port(80).port, 80);
+}
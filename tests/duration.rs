@@ -0,0 +1,48 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::time::Duration;
+
+#[derive(Debug, Default, FluentSetters)]
+struct Timeouts {
+    #[set(units)]
+    connect: Duration,
+
+    #[set(units(nanos, secs_f32))]
+    poll: Duration,
+}
+
+#[test]
+fn units() {
+    let timeouts = Timeouts::default().connect_secs(2);
+    assert_eq!(timeouts.connect, Duration::from_secs(2));
+
+    let timeouts = timeouts.connect_millis(1500);
+    assert_eq!(timeouts.connect, Duration::from_millis(1500));
+
+    let timeouts = timeouts.connect_micros(20);
+    assert_eq!(timeouts.connect, Duration::from_micros(20));
+
+    let timeouts = timeouts.connect_secs_f64(0.5);
+    assert_eq!(timeouts.connect, Duration::from_millis(500));
+
+    let timeouts = timeouts.poll_nanos(100).connect(Duration::from_secs(1));
+    assert_eq!(timeouts.poll, Duration::from_nanos(100));
+    assert_eq!(timeouts.connect, Duration::from_secs(1));
+
+    let timeouts = timeouts.poll_secs_f32(0.25);
+    assert_eq!(timeouts.poll, Duration::from_millis(250));
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Request {
+    #[set(units(secs), default = "Duration::from_secs(30)")]
+    timeout: Duration,
+}
+
+#[test]
+fn builder_units() {
+    let request = Request::builder().build().unwrap();
+    assert_eq!(request.timeout, Duration::from_secs(30));
+
+    let request = Request::builder().timeout_secs(5).build().unwrap();
+    assert_eq!(request.timeout, Duration::from_secs(5));
+}
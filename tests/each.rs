@@ -0,0 +1,87 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Command {
+    #[set(each = "arg", into)]
+    args: Vec<String>,
+
+    #[set(each = "stage")]
+    stages: VecDeque<u8>,
+
+    #[set(each = "flag")]
+    flags: HashSet<char>,
+
+    #[set(each = "tag", into)]
+    tags: BTreeSet<String>,
+}
+
+#[test]
+fn each() {
+    let command = Command::default()
+        .arg("-v")
+        .arg(String::from("--color"))
+        .stage(1)
+        .stage(2)
+        .flag('x')
+        .flag('x')
+        .tag("b")
+        .tag("a");
+
+    assert_eq!(command.args, ["-v", "--color"]);
+    assert_eq!(command.stages, [1, 2]);
+    assert_eq!(command.flags.len(), 1);
+    assert_eq!(command.tags.into_iter().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Request {
+    #[set(each = "header", into)]
+    headers: Vec<String>,
+
+    #[set(each = "param", into, default = r#"vec!["v=1".to_string()]"#)]
+    params: Vec<String>,
+}
+
+#[test]
+fn builder_each() {
+    let request = Request::builder()
+        .header("accept")
+        .header("host")
+        .build()
+        .unwrap();
+
+    assert_eq!(request.headers, ["accept", "host"]);
+    assert_eq!(request.params, ["v=1"]);
+}
+
+#[test]
+fn builder_each_default() {
+    let request = Request::builder().param("q=x").build().unwrap();
+
+    assert_eq!(request.params, ["v=1", "q=x"]);
+}
+
+#[derive(Debug, Default, FluentSetters)]
+struct Environment {
+    #[set(each = "var")]
+    vars: std::collections::HashMap<String, String>,
+
+    #[set(each = "label")]
+    labels: std::collections::BTreeMap<String, u32>,
+}
+
+#[test]
+fn each_map() {
+    let env = Environment::default()
+        .var("HOME", "/root")
+        .var("HOME", "/home")
+        .label("b", 2_u8)
+        .label("a", 1_u8);
+
+    assert_eq!(env.vars["HOME"], "/home");
+    assert_eq!(
+        env.labels.into_iter().collect::<Vec<_>>(),
+        [("a".to_string(), 1), ("b".to_string(), 2)]
+    );
+}
@@ -0,0 +1,75 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Command {
+    #[set(extend)]
+    args: Vec<String>,
+
+    #[set(extend)]
+    features: HashSet<String>,
+
+    #[set(extend)]
+    env: BTreeMap<String, String>,
+}
+
+#[test]
+fn extend() {
+    let command = Command::default()
+        .extend_args(vec!["-v"])
+        .extend_args(["--color", "always"].iter().copied())
+        .extend_features(Some("std"))
+        .extend_env(vec![("HOME", "/root")]);
+
+    assert_eq!(command.args, ["-v", "--color", "always"]);
+    assert!(command.features.contains("std"));
+    assert_eq!(command.env["HOME"], "/root");
+}
+
+#[test]
+fn replace() {
+    let command = Command::default()
+        .extend_args(vec!["-v"])
+        .replace_args(vec!["-q"]);
+
+    assert_eq!(command.args, ["-q"]);
+}
+
+#[derive(Debug, Default, FluentSetters)]
+struct Environment {
+    #[set(each = "var", extend, generic)]
+    vars: BTreeMap<String, String>,
+
+    #[set(extend, generic)]
+    paths: Vec<String>,
+}
+
+#[test]
+fn generic_extend() {
+    let environment = Environment::default()
+        .var::<&str, _>("HOME", String::from("/root"))
+        .extend_vars::<Vec<(&str, &str)>, _, _>(vec![("USER", "root")])
+        .extend_paths::<Option<&str>, _>(Some("/bin"))
+        .replace_paths::<Vec<_>, &str>(vec!["/usr/bin"]);
+
+    assert_eq!(environment.vars["HOME"], "/root");
+    assert_eq!(environment.vars["USER"], "root");
+    assert_eq!(environment.paths, ["/usr/bin"]);
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Request {
+    #[set(extend)]
+    headers: Vec<String>,
+}
+
+#[test]
+fn builder_extend() {
+    let request = Request::builder()
+        .extend_headers(vec!["accept"])
+        .extend_headers(vec!["host"])
+        .build()
+        .unwrap();
+
+    assert_eq!(request.headers, ["accept", "host"]);
+}
@@ -0,0 +1,37 @@
+use fluent_setters::FluentSetters;
+
+#[derive(Debug, Default, FluentSetters)]
+struct Options {
+    #[set(flag, negate = "no_colour")]
+    colour: bool,
+
+    #[set(flag)]
+    verbose: Option<bool>,
+}
+
+#[test]
+fn flag() {
+    let options = Options::default().enable_colour();
+    assert!(options.colour);
+
+    let options = options.no_colour();
+    assert!(!options.colour);
+
+    let options = options.colour_if(true);
+    assert!(options.colour);
+}
+
+#[test]
+fn tri_state_flag() {
+    let options = Options::default();
+    assert_eq!(options.verbose, None);
+
+    let options = options.enable_verbose();
+    assert_eq!(options.verbose, Some(true));
+
+    let options = options.disable_verbose();
+    assert_eq!(options.verbose, Some(false));
+
+    let options = options.verbose_if(true).unset_verbose();
+    assert_eq!(options.verbose, None);
+}
@@ -0,0 +1,35 @@
+use fluent_setters::FluentSetters;
+use std::{
+    cell::{Cell, RefCell},
+    sync::{Mutex, RwLock},
+};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Shared {
+    #[set(interior)]
+    count: Cell<u32>,
+
+    #[set(interior, into)]
+    name: RefCell<String>,
+
+    #[set(interior)]
+    limit: Mutex<u32>,
+
+    #[set(interior)]
+    level: RwLock<u8>,
+}
+
+#[test]
+fn interior() {
+    let shared = Shared::default();
+    shared
+        .set_count(1)
+        .set_name("shared")
+        .set_limit(10)
+        .set_level(3);
+
+    assert_eq!(shared.count.get(), 1);
+    assert_eq!(*shared.name.borrow(), "shared");
+    assert_eq!(*shared.limit.lock().unwrap(), 10);
+    assert_eq!(*shared.level.read().unwrap(), 3);
+}
@@ -0,0 +1,22 @@
+use fluent_setters::FluentSetters;
+
+#[derive(Debug, Default, PartialEq, FluentSetters)]
+struct Config {
+    #[set(strip, into, clear, maybe)]
+    name: Option<String>,
+}
+
+#[test]
+fn clear() {
+    let config = Config::default().name("config").clear_name();
+    assert_eq!(config.name, None);
+}
+
+#[test]
+fn maybe() {
+    let config = Config::default().maybe_name(Some("config"));
+    assert_eq!(config.name.as_deref(), Some("config"));
+
+    let config = config.maybe_name(None::<&str>);
+    assert_eq!(config.name, None);
+}
@@ -0,0 +1,46 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, FluentSetters)]
+struct Request {
+    #[set(each = "header", extend)]
+    headers: Option<HashMap<String, String>>,
+
+    #[set(each = "arg")]
+    args: Option<Vec<u32>>,
+}
+
+#[test]
+fn unset() {
+    let request = Request::default();
+    assert_eq!(request.headers, None);
+    assert_eq!(request.args, None);
+}
+
+#[test]
+fn each_initialises_collection() {
+    let request = Request::default().header("accept", "*/*").arg(1).arg(2);
+    assert_eq!(request.headers.unwrap()["accept"], "*/*");
+    assert_eq!(request.args, Some(vec![1, 2]));
+}
+
+#[test]
+fn extend_initialises_collection() {
+    let request = Request::default().extend_headers(Vec::<(String, String)>::new());
+    assert_eq!(request.headers, Some(HashMap::new()));
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Command {
+    #[set(each = "arg")]
+    args: Option<Vec<u32>>,
+}
+
+#[test]
+fn builder_each() {
+    assert_eq!(Command::builder().build().unwrap().args, None);
+    assert_eq!(
+        Command::builder().arg(1).build().unwrap().args,
+        Some(vec![1])
+    );
+}
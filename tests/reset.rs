@@ -0,0 +1,22 @@
+use fluent_setters::FluentSetters;
+
+#[derive(Debug, Default, PartialEq, FluentSetters)]
+struct Config {
+    #[set(reset, default = "8080")]
+    port: u16,
+
+    #[set(reset, into)]
+    host: String,
+}
+
+#[test]
+fn reset_to_default_expression() {
+    let config = Config::default().port(80).reset_port();
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn reset_to_default_trait() {
+    let config = Config::default().host("example.com").reset_host();
+    assert_eq!(config.host, "");
+}
@@ -0,0 +1,46 @@
+use fluent_setters::FluentSetters;
+
+#[derive(Debug, Default, PartialEq)]
+struct Placeholder;
+
+#[derive(Debug, PartialEq)]
+struct Http {
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, FluentSetters)]
+struct Client<T> {
+    #[set(retype)]
+    transport: T,
+
+    #[set]
+    retries: u32,
+
+    name: String,
+}
+
+impl Client<Placeholder> {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            transport: Placeholder,
+            retries: 0,
+            name: name.into(),
+        }
+    }
+}
+
+#[test]
+fn retype() {
+    let client: Client<Http> = Client::new("client")
+        .retries(3)
+        .transport(Http { port: 80 });
+
+    assert_eq!(
+        client,
+        Client {
+            transport: Http { port: 80 },
+            retries: 3,
+            name: "client".to_string(),
+        }
+    );
+}
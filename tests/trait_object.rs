@@ -0,0 +1,64 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::{fmt::Display, sync::Arc};
+
+#[derive(FluentSetters)]
+struct Router {
+    #[set]
+    filter: Box<dyn Fn(&str) -> bool + Send + Sync>,
+
+    #[set]
+    name: Arc<dyn Display + Send + Sync>,
+
+    #[set(strip)]
+    fallback: Option<Box<dyn Display>>,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            filter: Box::new(|_| true),
+            name: Arc::new("router"),
+            fallback: None,
+        }
+    }
+}
+
+#[test]
+fn trait_object_setters() {
+    let router = Router::default()
+        .filter(|path| path.starts_with('/'))
+        .name(42)
+        .fallback('?');
+
+    assert!((router.filter)("/index"));
+    assert!(!(router.filter)("index"));
+    assert_eq!(router.name.to_string(), "42");
+    assert_eq!(router.fallback.unwrap().to_string(), "?");
+}
+
+#[derive(Default, FluentSetters)]
+struct Logger {
+    #[set(strip, raw)]
+    sink: Option<Box<dyn Display>>,
+}
+
+#[test]
+fn raw_trait_object() {
+    let sink: Box<dyn Display> = Box::new("stderr");
+    let logger = Logger::default().sink(sink);
+
+    assert_eq!(logger.sink.unwrap().to_string(), "stderr");
+}
+
+#[derive(FluentBuilder)]
+struct Handler {
+    #[set(required)]
+    callback: Box<dyn Fn(u32) -> u32>,
+}
+
+#[test]
+fn builder_trait_object() {
+    let handler = Handler::builder().callback(|x| x + 1).build().unwrap();
+
+    assert_eq!((handler.callback)(1), 2);
+}
@@ -0,0 +1,39 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+use std::num::TryFromIntError;
+
+#[derive(Debug, Default, FluentSetters)]
+struct Pixel {
+    #[set(try_into)]
+    red: u8,
+
+    #[set(strip, try_into)]
+    alpha: Option<u8>,
+}
+
+#[test]
+fn try_into() -> Result<(), TryFromIntError> {
+    let pixel = Pixel::default().try_red(200_u32)?.try_alpha(10_i64)?;
+    assert_eq!(pixel.red, 200);
+    assert_eq!(pixel.alpha, Some(10));
+
+    assert!(pixel.try_red(256_u32).is_err());
+    Ok(())
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Port {
+    #[set(try_into)]
+    number: u16,
+}
+
+#[test]
+fn builder_try_into() {
+    let port = Port::builder()
+        .try_number(8080_u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(port.number, 8080);
+
+    assert!(Port::builder().try_number(-1_i32).is_err());
+}
@@ -0,0 +1,56 @@
+use fluent_setters::{FluentBuilder, FluentSetters};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Window {
+    #[set(args(width, height), into)]
+    size: (u32, u32),
+
+    #[set(destructure)]
+    range: (f64, f64),
+
+    #[set(const, destructure)]
+    origin: (i32,),
+
+    #[set]
+    span: (u8, u8),
+}
+
+#[test]
+fn tuple() {
+    let window = Window::default()
+        .size(640_u16, 480_u16)
+        .range(0.0, 1.0)
+        .span((1, 2));
+
+    assert_eq!(window.size, (640, 480));
+    assert_eq!(window.range, (0.0, 1.0));
+    assert_eq!(window.span, (1, 2));
+}
+
+#[test]
+fn const_tuple() {
+    const WINDOW: (i32,) = {
+        let window = Window {
+            size: (0, 0),
+            range: (0.0, 0.0),
+            origin: (0,),
+            span: (0, 0),
+        }
+        .origin(-1);
+        window.origin
+    };
+
+    assert_eq!(WINDOW, (-1,));
+}
+
+#[derive(Debug, FluentBuilder)]
+struct Viewport {
+    #[set(required, args(x, y))]
+    offset: (i32, i32),
+}
+
+#[test]
+fn builder_tuple() {
+    let viewport = Viewport::builder().offset(1, 2).build().unwrap();
+    assert_eq!(viewport.offset, (1, 2));
+}
@@ -0,0 +1,47 @@
+use fluent_setters::FluentBuilder;
+
+#[derive(Debug, PartialEq, FluentBuilder)]
+#[set(typestate)]
+struct Config<T> {
+    #[set(required, into)]
+    name: String,
+
+    #[set(required)]
+    transport: T,
+
+    #[set(strip)]
+    timeout: Option<u64>,
+
+    retries: u32,
+}
+
+#[test]
+fn build() {
+    let config = Config::builder()
+        .timeout(10)
+        .transport(8080_u16)
+        .name("server")
+        .build();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "server".to_string(),
+            transport: 8080,
+            timeout: Some(10),
+            retries: 0,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, FluentBuilder)]
+#[set(typestate)]
+struct Empty {
+    #[set]
+    a: u32,
+}
+
+#[test]
+fn no_required_fields() {
+    assert_eq!(Empty::builder().a(1).build(), Empty { a: 1 });
+}
@@ -0,0 +1,36 @@
+use fluent_setters::FluentSetters;
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+#[derive(Debug, Default, FluentSetters)]
+struct Node<'a> {
+    #[set(wrap, into)]
+    id: Box<u64>,
+
+    #[set(wrap)]
+    weight: Rc<u32>,
+
+    #[set(wrap)]
+    shared: Arc<Vec<u8>>,
+
+    #[set(wrap)]
+    label: Cow<'a, str>,
+
+    #[set(wrap)]
+    next: Option<Box<u32>>,
+}
+
+#[test]
+fn wrap() {
+    let node = Node::default()
+        .id(1_u32)
+        .weight(3)
+        .shared(vec![1, 2])
+        .label(String::from("label"))
+        .next(4);
+
+    assert_eq!(*node.id, 1);
+    assert_eq!(*node.weight, 3);
+    assert_eq!(*node.shared, [1, 2]);
+    assert_eq!(node.label, "label");
+    assert_eq!(node.next, Some(Box::new(4)));
+}